@@ -0,0 +1,188 @@
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::path::PathBuf;
+
+use crate::error::{Error, Result};
+use crate::puzzle::{Answer, PuzzleId};
+use crate::util::parse;
+
+const ANSWERS_DIR: &str = "answers";
+
+/// The known-good answers for one day, stored in `answers/<year>/<day>.toml` as one table per
+/// input:
+///
+/// ```toml
+/// [real]
+/// 1 = 54573
+/// 2 = "some text"
+///
+/// [example-1]
+/// 1 = 142
+/// ```
+///
+/// Only this subset of TOML is understood: table headers, integer keys, and integer or basic
+/// string values. A string starting with `error: ` records that the part is expected to fail
+/// on that input with an error ending in the rest of it.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Answers {
+    /// input label -> part number (1-based) -> answer
+    pub inputs: BTreeMap<String, BTreeMap<usize, Answer>>,
+}
+
+impl Answers {
+    pub fn path(id: PuzzleId) -> PathBuf {
+        PathBuf::from(ANSWERS_DIR).join(id.path()).with_extension("toml")
+    }
+
+    /// Loads the answers for puzzle `id`. A missing file is the same as an empty one.
+    pub fn load(id: PuzzleId) -> Result<Self> {
+        let path = Self::path(id);
+        match std::fs::read_to_string(&path) {
+            Ok(text) => Self::parse(&text),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(Error::io(path.display().to_string(), e)),
+        }
+    }
+
+    pub fn save(&self, id: PuzzleId) -> Result<()> {
+        let path = Self::path(id);
+        let write = || {
+            if let Some(dir) = path.parent() {
+                std::fs::create_dir_all(dir)?;
+            }
+            std::fs::write(&path, self.to_string())
+        };
+        write().map_err(|e| Error::io(path.display().to_string(), e))
+    }
+
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut answers = Self::default();
+        let mut table = None;
+
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let at_line = |e: Error| e.at_line(i + 1);
+
+            if let Some(header) = line.strip_prefix('[') {
+                let label = header
+                    .strip_suffix(']')
+                    .ok_or_else(|| at_line(Error::parse("`[<input>]`", line)))?;
+                answers.inputs.entry(label.to_string()).or_default();
+                table = Some(label.to_string());
+                continue;
+            }
+
+            let (part, value) = line
+                .split_once('=')
+                .ok_or_else(|| at_line(Error::parse("`<part> = <answer>`", line)))?;
+            let part = parse(part.trim()).map_err(at_line)?;
+            let value = parse_value(value.trim()).map_err(at_line)?;
+
+            let label = table
+                .as_ref()
+                .ok_or_else(|| at_line(Error::missing("an `[<input>]` header before the first answer")))?;
+            answers.inputs.entry(label.clone()).or_default().insert(part, value);
+        }
+
+        Ok(answers)
+    }
+
+    pub fn get(&self, input: &str, part: usize) -> Option<&Answer> {
+        self.inputs.get(input).and_then(|parts| parts.get(&part))
+    }
+
+    pub fn set(&mut self, input: &str, part: usize, answer: Answer) {
+        self.inputs.entry(input.to_string()).or_default().insert(part, answer);
+    }
+}
+
+fn parse_value(s: &str) -> Result<Answer> {
+    if let Some(quoted) = s.strip_prefix('"') {
+        let body = quoted
+            .strip_suffix('"')
+            .ok_or_else(|| Error::parse("a closing `\"`", s))?;
+        return Ok(Answer::Str(body.replace("\\\"", "\"").replace("\\\\", "\\")));
+    }
+
+    match parse::<i64>(s) {
+        Ok(n) => Ok(Answer::Int(n)),
+        Err(_) => parse::<i128>(s)
+            .map(Answer::BigInt)
+            .map_err(|_| Error::parse("an integer or a quoted string", s)),
+    }
+}
+
+impl Display for Answers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let tables = self.inputs.iter()
+            .map(|(label, parts)| {
+                let lines = parts.iter()
+                    .map(|(part, answer)| match answer {
+                        Answer::Str(s) => {
+                            format!("{part} = \"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+                        },
+                        _ => format!("{part} = {answer}"),
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                format!("[{label}]\n{lines}\n")
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        write!(f, "{tables}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_tables_comments_and_quoting() {
+        let text = "\
+# recorded by aoc check
+[real]
+1 = 54573
+2 = \"say \\\"hi\\\"\"
+
+[example-1]
+1 = -3
+2 = 170141183460469231731687303715884105727
+";
+        let answers = Answers::parse(text).unwrap();
+        assert_eq!(answers.get("real", 1), Some(&Answer::Int(54573)));
+        assert_eq!(answers.get("real", 2), Some(&Answer::Str("say \"hi\"".to_string())));
+        assert_eq!(answers.get("example-1", 1), Some(&Answer::Int(-3)));
+        assert_eq!(answers.get("example-1", 2), Some(&Answer::BigInt(i128::MAX)));
+        assert_eq!(answers.get("example-2", 1), None);
+    }
+
+    #[test]
+    fn round_trips_through_display() {
+        let mut answers = Answers::default();
+        answers.set("real", 1, Answer::Int(7));
+        answers.set("real", 2, Answer::Str("back\\slash \"quoted\"".to_string()));
+        answers.set("example-1", 1, Answer::BigInt(i128::MIN));
+
+        assert_eq!(Answers::parse(&answers.to_string()).unwrap(), answers);
+    }
+
+    #[test]
+    fn reports_the_line_of_bad_entries() {
+        let line_of = |text| match Answers::parse(text) {
+            Err(Error::Parse { line, .. } | Error::Missing { line, .. }) => line,
+            other => panic!("expected an error, got {other:?}"),
+        };
+
+        assert_eq!(line_of("[real]\n1 = 2\n2 3\n"), Some(3));
+        assert_eq!(line_of("[real\n"), Some(1));
+        assert_eq!(line_of("[real]\none = 2\n"), Some(2));
+        assert_eq!(line_of("[real]\n1 = \"unterminated\n"), Some(2));
+        assert_eq!(line_of("[real]\n1 = 1.5\n"), Some(2));
+        assert_eq!(line_of("# no header yet\n1 = 2\n"), Some(2));
+    }
+}
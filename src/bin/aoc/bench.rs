@@ -0,0 +1,78 @@
+use std::time::{Duration, Instant};
+
+use advent_2023::days;
+use advent_2023::input::Source;
+
+use crate::guarded;
+
+struct Stats {
+    min: Duration,
+    median: Duration,
+    mean: Duration,
+}
+
+impl Stats {
+    fn from_samples(mut samples: Vec<Duration>) -> Self {
+        samples.sort();
+        let total: Duration = samples.iter().sum();
+
+        Stats {
+            min: samples[0],
+            median: samples[samples.len() / 2],
+            mean: total / samples.len() as u32,
+        }
+    }
+}
+
+/// Times `f` over `n` runs, stopping at the first failure.
+fn sample<T>(n: u32, mut f: impl FnMut() -> Result<T, String>) -> Result<Stats, String> {
+    let samples = (0..n)
+        .map(|_| {
+            let start = Instant::now();
+            f().map(|_| start.elapsed())
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(Stats::from_samples(samples))
+}
+
+fn print_row(day: u32, step: &str, stats: &Stats) {
+    println!("{day:>3}  {step:<10} {:>10.2?} {:>10.2?} {:>10.2?}", stats.min, stats.median, stats.mean);
+}
+
+/// Runs preprocessing and then each part `n` times for every selected day. Returns whether every
+/// day ran cleanly.
+pub fn bench(selected: &[u32], source: &Source, n: u32) -> bool {
+    let mut ok = true;
+
+    println!("day  {:<10} {:>10} {:>10} {:>10}", "step", "min", "median", "mean");
+
+    for &day in selected {
+        let Some(solver) = days::solver_for(day) else { continue };
+        let input = source.describe(solver.id());
+
+        let result = source
+            .read(solver.id())
+            .map_err(|e| e.to_string())
+            .and_then(|contents| {
+                let stats = sample(n, || guarded(&input, || solver.prepare(&contents)))?;
+                print_row(day, "preprocess", &stats);
+
+                let prepared = guarded(&input, || solver.prepare(&contents))?;
+                for i in 0..prepared.parts() {
+                    let stats = sample(n, || guarded(&input, || prepared.run(i)))?;
+                    print_row(day, &format!("part {}", i + 1), &stats);
+                }
+
+                Ok(())
+            });
+
+        if let Err(e) = result {
+            println!("{day:>3}  error");
+            eprintln!("day {day}: {e}");
+            ok = false;
+        }
+    }
+
+    ok
+}
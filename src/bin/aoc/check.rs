@@ -0,0 +1,152 @@
+use std::collections::BTreeSet;
+
+use advent_2023::answers::Answers;
+use advent_2023::days;
+use advent_2023::input::{self, Source};
+use advent_2023::puzzle::{Answer, PuzzleId};
+
+use crate::{run_day, PartReport};
+
+enum Status {
+    Pass,
+    Fail { expected: Answer, got: Option<Answer> },
+    Missing { got: Answer },
+    Error(String),
+}
+
+/// Every input worth checking for a day: the real input if it exists, every example on disk,
+/// and anything that already has recorded answers (so a deleted input shows up as an error).
+fn inputs_for(id: PuzzleId, answers: &Answers) -> Vec<Source> {
+    let mut labels = BTreeSet::new();
+
+    if Source::Real.path(id).is_some_and(|p| p.exists()) {
+        labels.insert("real".to_string());
+    }
+    for n in input::examples(id) {
+        labels.extend(Source::Example(n).label());
+    }
+    labels.extend(answers.inputs.keys().cloned());
+
+    labels.iter().filter_map(|l| Source::from_label(l)).collect()
+}
+
+/// Whether `label`'s recorded answer for `part` is `"error: ..."` with the end of `error`, for
+/// inputs a part deliberately refuses to solve.
+fn expects_error(answers: &Answers, label: &str, part: usize, error: &str) -> bool {
+    match answers.get(label, part) {
+        Some(Answer::Str(s)) => s.strip_prefix("error: ").is_some_and(|e| error.ends_with(e)),
+        _ => false,
+    }
+}
+
+fn compare(answers: &Answers, label: &str, got: &[PartReport]) -> Vec<(usize, Status)> {
+    let last_recorded = answers.inputs.get(label)
+        .and_then(|parts| parts.keys().max().copied())
+        .unwrap_or(0);
+    let last_part = std::cmp::max(got.len(), last_recorded);
+
+    (1..=last_part)
+        .map(|part| {
+            let got = match got.get(part - 1).map(|p| &p.answer) {
+                Some(Err(e)) if expects_error(answers, label, part, e) => return (part, Status::Pass),
+                Some(Err(e)) => return (part, Status::Error(e.clone())),
+                Some(Ok(answer)) => Some(answer.clone()),
+                None => None,
+            };
+
+            let status = match (answers.get(label, part), got) {
+                (Some(expected), Some(got)) if expected.to_string() == got.to_string() => Status::Pass,
+                (Some(expected), got) => Status::Fail { expected: expected.clone(), got },
+                (None, Some(got)) => Status::Missing { got },
+                (None, None) => unreachable!("parts only go up to the last recorded or computed one"),
+            };
+            (part, status)
+        })
+        .collect()
+}
+
+/// Checks `selected` days against their answer files, optionally recording answers for parts
+/// that have none yet. Returns whether everything that has an answer matched.
+pub fn check(selected: &[u32], record: bool) -> bool {
+    let (mut passed, mut failed, mut missing, mut errors) = (0, 0, 0, 0);
+
+    println!("Advent of Code {}\n", days::YEAR);
+
+    for &day in selected {
+        let Some(solver) = days::solver_for(day) else { continue };
+        let id = solver.id();
+
+        let mut answers = match Answers::load(id) {
+            Ok(answers) => answers,
+            Err(e) => {
+                println!("{day:>3} {e}");
+                errors += 1;
+                continue;
+            },
+        };
+        let mut changed = false;
+
+        let sources = inputs_for(id, &answers);
+        if sources.is_empty() {
+            println!("{day:>3} no inputs or recorded answers");
+            missing += 1;
+        }
+
+        for source in sources {
+            // inputs_for only returns sources that have labels
+            let label = source.label().unwrap();
+
+            let got = match run_day(day, &source) {
+                Ok(report) => report.parts,
+                Err(e) => {
+                    println!("{day:>3} {label:<10} error: {e}");
+                    errors += 1;
+                    continue;
+                },
+            };
+
+            for (part, status) in compare(&answers, &label, &got) {
+                let result = match status {
+                    Status::Pass => {
+                        passed += 1;
+                        "pass".to_string()
+                    },
+                    Status::Fail { expected, got } => {
+                        failed += 1;
+                        match got {
+                            Some(got) => format!("FAIL: expected {expected}, got {got}"),
+                            None => format!("FAIL: expected {expected}, got nothing"),
+                        }
+                    },
+                    Status::Missing { got } => {
+                        missing += 1;
+                        let note = if record { "recorded" } else { "missing" };
+                        let line = format!("{note} (got {got})");
+                        if record {
+                            answers.set(&label, part, got);
+                            changed = true;
+                        }
+                        line
+                    },
+                    Status::Error(e) => {
+                        errors += 1;
+                        format!("error: {e}")
+                    },
+                };
+                println!("{day:>3} {label:<10} part {part}  {result}");
+            }
+        }
+
+        if changed {
+            if let Err(e) = answers.save(id) {
+                println!("{day:>3} {e}");
+                errors += 1;
+            }
+        }
+    }
+
+    println!();
+    println!("{passed} passed, {failed} failed, {missing} missing, {errors} errors");
+
+    failed == 0 && errors == 0
+}
@@ -0,0 +1,153 @@
+use std::path::Path;
+
+use advent_2023::config::Config;
+use advent_2023::days;
+use advent_2023::input::Source;
+use advent_2023::puzzle::PuzzleId;
+
+const USER_AGENT: &str = "advent-2023 aoc fetch";
+
+/// What the site sends back, with a 400, when the session cookie is missing or stale.
+const LOGGED_OUT: &str = "Puzzle inputs differ by user";
+
+/// Downloads the input for `id` from the site at `base_url`, sending the session cookie.
+fn download(base_url: &str, id: PuzzleId, session: &str) -> Result<String, String> {
+    let url = format!("{}/{}/day/{}/input", base_url.trim_end_matches('/'), id.year, id.day);
+    let rejected = || format!("{url}: the session token was rejected; log in again and update it");
+
+    let response = ureq::get(&url)
+        .set("Cookie", &format!("session={session}"))
+        .set("User-Agent", USER_AGENT)
+        .call();
+    let body = match response {
+        Ok(response) => response.into_string().map_err(|e| format!("{url}: {e}"))?,
+        Err(ureq::Error::Status(status, response)) => {
+            let body = response.into_string().unwrap_or_default();
+            if body.starts_with(LOGGED_OUT) {
+                return Err(rejected());
+            }
+            return Err(format!("{url}: HTTP {status}: {}", body.trim()));
+        },
+        Err(e) => return Err(format!("{url}: {e}")),
+    };
+
+    if body.starts_with(LOGGED_OUT) {
+        return Err(rejected());
+    }
+    if body.is_empty() {
+        return Err(format!("{url}: empty response"));
+    }
+    Ok(body)
+}
+
+/// Downloads the input for `id` into `path`. Writes next to it and renames, so a failed write
+/// never leaves half an input behind to be mistaken for a cached one.
+fn fetch_into(path: &Path, base_url: &str, id: PuzzleId, session: &str) -> Result<(), String> {
+    let body = download(base_url, id, session)?;
+
+    let partial = path.with_extension("part");
+    std::fs::create_dir_all(path.parent().expect("inputs are in a directory"))
+        .and_then(|_| std::fs::write(&partial, body))
+        .and_then(|_| std::fs::rename(&partial, path))
+        .map_err(|e| format!("{}: {e}", path.display()))
+}
+
+/// Downloads the real input for `day` into the input dir, unless it's already there. Inputs
+/// never change, so an existing file is never fetched again.
+pub fn fetch(day: u32) -> Result<(), String> {
+    let id = PuzzleId::new(days::YEAR, day);
+    let path = Source::Real.path(id).expect("real inputs are files");
+    if path.exists() {
+        println!("{} already exists", path.display());
+        return Ok(());
+    }
+
+    let config = Config::load().map_err(|e| format!("{}: {e}", Config::path().display()))?;
+    let session = config.session.ok_or_else(|| {
+        format!("no session token; add `session = \"...\"` to {}", Config::path().display())
+    })?;
+
+    fetch_into(&path, &config.base_url, id, &session)?;
+    println!("fetched {}", path.display());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::path::PathBuf;
+    use std::thread::JoinHandle;
+
+    use super::*;
+
+    /// Serves one canned response on a local port and hands back the request headers.
+    fn stub(status: &str, body: &'static str) -> (String, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let status = status.to_string();
+
+        let server = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(&stream);
+            let headers = (&mut reader)
+                .lines()
+                .map(|line| line.unwrap())
+                .take_while(|line| !line.is_empty())
+                .collect();
+
+            write!(
+                &stream,
+                "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len(),
+            )
+            .unwrap();
+            headers
+        });
+
+        (base_url, server)
+    }
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc-fetch-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn writes_the_input_and_sends_the_session() {
+        let (base_url, server) = stub("200 OK", "1 2 3\n");
+        let dir = scratch_dir("ok");
+        let id = PuzzleId::new(2023, 7);
+        let path = dir.join("inputs").join(id.path());
+
+        fetch_into(&path, &base_url, id, "s3cret").unwrap();
+
+        let headers = server.join().unwrap();
+        assert_eq!(headers[0], "GET /2023/day/7/input HTTP/1.1");
+        assert!(headers.iter().any(|h| h.eq_ignore_ascii_case("cookie: session=s3cret")));
+        assert_eq!(std::fs::read_to_string(dir.join("inputs/2023/7")).unwrap(), "1 2 3\n");
+        assert!(!path.with_extension("part").exists());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn rejects_error_responses() {
+        let id = PuzzleId::new(2023, 1);
+
+        for (status, body) in [
+            ("404 Not Found", "404 Not Found"),
+            ("400 Bad Request", "Puzzle inputs differ by user.  Please log in to get your puzzle input."),
+            ("200 OK", "Puzzle inputs differ by user.  Please log in to get your puzzle input."),
+            ("200 OK", ""),
+        ] {
+            let (base_url, server) = stub(status, body);
+            let dir = scratch_dir("err");
+            let path = dir.join("inputs").join(id.path());
+
+            assert!(fetch_into(&path, &base_url, id, "stale").is_err(), "{status}: {body:?}");
+            server.join().unwrap();
+            assert!(!path.exists(), "{status}: {body:?}");
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use advent_2023::puzzle::PuzzleId;
+
+use crate::DayReport;
+
+/// Quotes `s` as a JSON string.
+fn string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Prints one JSON object per line for each part of each day:
+///
+/// ```json
+/// {"year":2023,"day":5,"title":"...","part":1,"answer":"35","duration_ns":12345,"status":"ok"}
+/// {"year":2023,"day":5,"title":"...","part":2,"answer":null,"duration_ns":678,"status":"error","error":"..."}
+/// ```
+///
+/// A day that fails before any part runs gets a single record with a null title and part.
+/// Answers are always strings, so big integers survive JSON parsers that only have doubles.
+pub fn print_records(id: PuzzleId, report: &Result<DayReport, String>) {
+    let PuzzleId { year, day } = id;
+
+    match report {
+        Ok(report) => {
+            let title = string(&report.title);
+            for (i, part) in report.parts.iter().enumerate() {
+                let duration = part.time.as_nanos();
+                let (answer, status) = match &part.answer {
+                    Ok(answer) => (string(&answer.to_string()), r#""status":"ok""#.to_string()),
+                    Err(e) => ("null".to_string(), format!(r#""status":"error","error":{}"#, string(e))),
+                };
+                println!(
+                    r#"{{"year":{year},"day":{day},"title":{title},"part":{},"answer":{answer},"duration_ns":{duration},{status}}}"#,
+                    i + 1
+                );
+            }
+        },
+        Err(e) => {
+            println!(
+                r#"{{"year":{year},"day":{day},"title":null,"part":null,"answer":null,"duration_ns":null,"status":"error","error":{}}}"#,
+                string(e)
+            );
+        },
+    }
+}
@@ -0,0 +1,358 @@
+use std::panic::{self, AssertUnwindSafe};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use clap::{Parser, Subcommand, ValueEnum};
+
+use advent_2023::days;
+use advent_2023::input::Source;
+use advent_2023::log;
+use advent_2023::puzzle::{Answer, PuzzleId};
+
+mod bench;
+mod check;
+mod fetch;
+mod json;
+mod new;
+
+#[derive(Parser, Debug)]
+struct Args {
+    #[command(subcommand)]
+    command: Command,
+
+    /// Show solver debug output on stderr; repeat (`-vv`) for step-by-step traces
+    #[arg(short, long, global = true, action = clap::ArgAction::Count)]
+    verbose: u8,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Run one or more days and print a table of answers
+    Run {
+        /// A single day (`5`), an inclusive range (`10..15`) or `all`
+        days: Days,
+
+        #[command(flatten)]
+        input: InputArgs,
+
+        /// Instead of printing answers, run preprocessing and each part N times and report
+        /// min/median/mean durations
+        #[arg(long, value_name = "N", value_parser = clap::value_parser!(u32).range(1..))]
+        bench: Option<u32>,
+
+        /// How to print answers. Diagnostics always go to stderr.
+        #[arg(long, value_enum, default_value_t = Format::Table, conflicts_with = "bench")]
+        format: Format,
+    },
+
+    /// Compare days against the answers recorded in `answers/<year>/<day>.toml`
+    Check {
+        /// A single day (`5`), an inclusive range (`10..15`) or `all`
+        #[arg(default_value = "all")]
+        days: Days,
+
+        /// Record the current answer for any part that has none yet
+        #[arg(long)]
+        record: bool,
+    },
+
+    /// Download a day's real input into the input dir, using the session token in `aoc.toml`
+    Fetch {
+        #[arg(value_parser = clap::value_parser!(u32).range(1..=25))]
+        day: u32,
+    },
+
+    /// Start a new day: write `src/days/day_NN.rs` from a template, register it, and create an
+    /// empty example and answers file
+    New {
+        #[arg(value_parser = clap::value_parser!(u32).range(1..=25))]
+        day: u32,
+
+        /// The puzzle's title, as shown on the site
+        #[arg(long, default_value = "")]
+        title: String,
+
+        #[command(flatten)]
+        layout: LayoutArgs,
+    },
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    /// One row per day
+    Table,
+    /// One JSON object per line for each part
+    Json,
+}
+
+/// Picks the input for each day. Without any of these, days read `<input dir>/<year>/<day>`,
+/// where the input dir is `$AOC_INPUT_DIR` or `inputs`. Inputs used to live directly in the input
+/// dir; move any `<input dir>/<day>` files into the year's directory.
+#[derive(clap::Args, Debug)]
+#[group(multiple = false)]
+struct InputArgs {
+    /// Read `examples/<year>/<day>/<N>.txt` instead of the real input
+    #[arg(short, long, value_name = "N")]
+    example: Option<u32>,
+
+    /// Read this file instead of the real input (single day only)
+    #[arg(short, long, value_name = "PATH")]
+    input: Option<PathBuf>,
+
+    /// Read the input from stdin (single day only)
+    #[arg(long)]
+    stdin: bool,
+}
+
+impl InputArgs {
+    fn source(&self) -> Source {
+        if let Some(n) = self.example {
+            Source::Example(n)
+        } else if let Some(path) = &self.input {
+            Source::File(path.clone())
+        } else if self.stdin {
+            Source::Stdin
+        } else {
+            Source::Real
+        }
+    }
+}
+
+/// Picks the template for `aoc new`. Without any of these, the input is read as lines.
+#[derive(clap::Args, Debug)]
+#[group(multiple = false)]
+struct LayoutArgs {
+    /// One record per line
+    #[arg(long)]
+    lines: bool,
+
+    /// A grid of characters, read into a `Map<char>`
+    #[arg(long)]
+    grid: bool,
+
+    /// Blocks of lines separated by blank lines
+    #[arg(long)]
+    paragraphs: bool,
+}
+
+impl LayoutArgs {
+    fn layout(&self) -> new::Layout {
+        if self.grid {
+            new::Layout::Grid
+        } else if self.paragraphs {
+            new::Layout::Paragraphs
+        } else {
+            new::Layout::Lines
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Days {
+    All,
+    // Both ends are inclusive.
+    Range(u32, u32),
+}
+
+impl Days {
+    fn selected(&self) -> Vec<u32> {
+        days::registered_days()
+            .into_iter()
+            .filter(|d| match self {
+                Days::All => true,
+                Days::Range(lo, hi) => lo <= d && d <= hi,
+            })
+            .collect()
+    }
+}
+
+impl FromStr for Days {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse = |n: &str| {
+            n.trim()
+                .parse::<u32>()
+                .map_err(|_| format!("invalid day `{n}`"))
+        };
+
+        if s == "all" {
+            Ok(Days::All)
+        } else if let Some((lo, hi)) = s.split_once("..") {
+            Ok(Days::Range(parse(lo)?, parse(hi)?))
+        } else {
+            let day = parse(s)?;
+            Ok(Days::Range(day, day))
+        }
+    }
+}
+
+pub(crate) struct PartReport {
+    pub answer: Result<Answer, String>,
+    pub time: Duration,
+}
+
+pub(crate) struct DayReport {
+    pub title: String,
+    pub preprocess: Duration,
+    pub parts: Vec<PartReport>,
+}
+
+/// Runs `f`, turning errors and panics into a diagnostic naming `input`. Catching panics means
+/// one broken day doesn't stop a whole `run all` or `check`.
+pub(crate) fn guarded<T>(input: &str, f: impl FnOnce() -> advent_2023::error::Result<T>) -> Result<T, String> {
+    // The default hook would print the message and a backtrace before we get to report it, so
+    // silence it while `f` runs.
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    panic::set_hook(hook);
+
+    match result {
+        Ok(Ok(v)) => Ok(v),
+        Ok(Err(e)) => Err(format!("{input}: {e}")),
+        Err(payload) => {
+            let message = payload
+                .downcast_ref::<&str>()
+                .copied()
+                .or_else(|| payload.downcast_ref::<String>().map(String::as_str));
+            match message {
+                Some(message) => Err(format!("{input}: panicked: {message}")),
+                None => Err(format!("{input}: panicked")),
+            }
+        },
+    }
+}
+
+/// Runs a single day, timing preprocessing and each part. An error before any part runs fails
+/// the whole day; after that, each part succeeds or fails on its own.
+pub(crate) fn run_day(day: u32, source: &Source) -> Result<DayReport, String> {
+    let solver = days::solver_for(day).ok_or(format!("day {day} is not registered"))?;
+    let contents = source.read(solver.id()).map_err(|e| e.to_string())?;
+    let input = source.describe(solver.id());
+
+    let start = Instant::now();
+    let prepared = guarded(&input, || solver.prepare(&contents))?;
+    let preprocess = start.elapsed();
+
+    let parts = (0..prepared.parts())
+        .map(|i| {
+            let start = Instant::now();
+            let answer = guarded(&input, || prepared.run(i));
+            PartReport { answer, time: start.elapsed() }
+        })
+        .collect();
+
+    Ok(DayReport {
+        title: solver.title().to_string(),
+        preprocess,
+        parts,
+    })
+}
+
+/// Prints one row per day with each part's answer, how long it took, and the puzzle's title.
+/// Returns whether every day ran cleanly.
+fn print_table(selected: &[u32], source: &Source) -> bool {
+    let rows: Vec<_> = selected.iter().map(|&d| (d, run_day(d, source))).collect();
+
+    let parts = rows
+        .iter()
+        .filter_map(|(_, r)| r.as_ref().ok().map(|r| r.parts.len()))
+        .max()
+        .unwrap_or(0);
+
+    let mut ok = true;
+    let header = (1..=parts).map(|p| format!(" | part {p:<20}")).collect::<String>();
+    println!("Advent of Code {}\n", days::YEAR);
+    println!("day | preprocess{header} | title");
+
+    for (day, result) in rows {
+        match result {
+            Ok(report) => {
+                let cells = report.parts.iter()
+                    .map(|part| {
+                        let answer = part.answer.as_ref().map_or("error".to_string(), |a| a.to_string());
+                        format!(" | {answer:<16} {:>8.2?}", part.time)
+                    })
+                    .collect::<String>();
+                println!("{day:>3} | {:>10.2?}{cells} | {}", report.preprocess, report.title);
+
+                for e in report.parts.iter().filter_map(|p| p.answer.as_ref().err()) {
+                    eprintln!("day {day}: {e}");
+                    ok = false;
+                }
+            },
+            Err(e) => {
+                println!("{day:>3} | error");
+                eprintln!("day {day}: {e}");
+                ok = false;
+            },
+        }
+    }
+
+    ok
+}
+
+/// Prints a JSON record per part. Returns whether every day ran cleanly.
+fn print_json(selected: &[u32], source: &Source) -> bool {
+    let mut ok = true;
+
+    for &day in selected {
+        let report = run_day(day, source);
+        ok &= report.as_ref().is_ok_and(|r| r.parts.iter().all(|p| p.answer.is_ok()));
+        json::print_records(PuzzleId::new(days::YEAR, day), &report);
+    }
+
+    ok
+}
+
+fn main() {
+    let args = Args::parse();
+    log::set_verbosity(args.verbose);
+
+    match args.command {
+        Command::Run { days, input, bench, format } => {
+            let selected = days.selected();
+            if selected.is_empty() {
+                eprintln!("no registered days match");
+                std::process::exit(1);
+            }
+
+            let source = input.source();
+            if matches!(source, Source::File(_) | Source::Stdin) && selected.len() > 1 {
+                eprintln!("`--input` and `--stdin` can only be used with a single day");
+                std::process::exit(1);
+            }
+
+            let ok = match (bench, format) {
+                (Some(n), _) => bench::bench(&selected, &source, n),
+                (None, Format::Table) => print_table(&selected, &source),
+                (None, Format::Json) => print_json(&selected, &source),
+            };
+            if !ok {
+                std::process::exit(1);
+            }
+        },
+
+        Command::Check { days, record } => {
+            if !check::check(&days.selected(), record) {
+                std::process::exit(1);
+            }
+        },
+
+        Command::New { day, title, layout } => {
+            if let Err(e) = new::new(day, &title, layout.layout()) {
+                eprintln!("day {day}: {e}");
+                std::process::exit(1);
+            }
+        },
+
+        Command::Fetch { day } => {
+            if let Err(e) = fetch::fetch(day) {
+                eprintln!("day {day}: {e}");
+                std::process::exit(1);
+            }
+        },
+    }
+}
@@ -0,0 +1,279 @@
+use std::path::Path;
+
+use advent_2023::answers::Answers;
+use advent_2023::days;
+use advent_2023::input::Source;
+use advent_2023::puzzle::PuzzleId;
+
+const DAYS_DIR: &str = "src/days";
+
+/// What the new day's input looks like, which decides how it's split and preprocessed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Layout {
+    /// One record per line.
+    Lines,
+    /// A grid of characters, read into a `Map<char>`.
+    Grid,
+    /// Blocks of lines separated by blank lines.
+    Paragraphs,
+}
+
+const LINES_TEMPLATE: &str = r#"use crate::error::Result;
+use crate::puzzle::{Puzzle, PuzzleId, Solver};
+use crate::util::map_lines;
+
+fn parse_line(line: &str) -> Result<String> {
+    Ok(line.to_string())
+}
+
+fn a(_lines: &Vec<String>) -> u64 {
+    todo!()
+}
+
+fn b(_lines: &Vec<String>) -> u64 {
+    todo!()
+}
+
+pub fn solver() -> Box<dyn Solver> {
+    Box::new(Puzzle {
+        id: PuzzleId::new({year}, {day}),
+        title: {title},
+        parts: vec![a, b],
+        delimiter: '\n',
+        preprocess: |text| map_lines(&text, parse_line),
+    })
+}
+"#;
+
+const GRID_TEMPLATE: &str = r#"use crate::puzzle::{Puzzle, PuzzleId, Solver};
+use crate::twod::{char_map_from_strings, Map};
+
+fn a(_map: &Map<char>) -> u64 {
+    todo!()
+}
+
+fn b(_map: &Map<char>) -> u64 {
+    todo!()
+}
+
+pub fn solver() -> Box<dyn Solver> {
+    Box::new(Puzzle {
+        id: PuzzleId::new({year}, {day}),
+        title: {title},
+        parts: vec![a, b],
+        delimiter: '\n',
+        preprocess: char_map_from_strings,
+    })
+}
+"#;
+
+const PARAGRAPHS_TEMPLATE: &str = r#"use crate::puzzle::{Paragraphs, Puzzle, PuzzleId, Solver};
+
+fn a(_blocks: &Vec<Vec<String>>) -> u64 {
+    todo!()
+}
+
+fn b(_blocks: &Vec<Vec<String>>) -> u64 {
+    todo!()
+}
+
+pub fn solver() -> Box<dyn Solver> {
+    Box::new(Puzzle {
+        id: PuzzleId::new({year}, {day}),
+        title: {title},
+        parts: vec![a, b],
+        delimiter: Paragraphs,
+        preprocess: |blocks| Ok(blocks),
+    })
+}
+"#;
+
+/// Inserts `line` into the run of lines starting with `prefix`, before the first one with a
+/// larger `key`. With no run yet, it goes right after the last line starting with `anchor`.
+fn insert_sorted(
+    lines: &mut Vec<String>,
+    prefix: &str,
+    anchor: &str,
+    line: String,
+    key: impl Fn(&str) -> u32,
+) -> Result<(), String> {
+    let run: Vec<usize> = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, l)| l.starts_with(prefix))
+        .map(|(i, _)| i)
+        .collect();
+
+    let at = match run.last() {
+        Some(&last) => run
+            .iter()
+            .find(|&&i| key(&lines[i]) > key(&line))
+            .copied()
+            .unwrap_or(last + 1),
+        None => {
+            let anchor = lines
+                .iter()
+                .rposition(|l| l.starts_with(anchor))
+                .ok_or_else(|| format!("no `{prefix}` lines, nor `{anchor}` to put the first after"))?;
+            anchor + 1
+        },
+    };
+    lines.insert(at, line);
+    Ok(())
+}
+
+/// The registry source `text` with `mod day_NN;` and `entry(N, day_NN::solver),` added, each in
+/// day order.
+fn registered(text: &str, day: u32, module: &str) -> Result<String, String> {
+    let mut lines: Vec<String> = text.lines().map(String::from).collect();
+
+    // `day_07b` sorts with day 7
+    let mod_day = |l: &str| {
+        l.trim_start_matches("mod day_")
+            .trim_end_matches(';')
+            .trim_end_matches(|c: char| c.is_ascii_alphabetic())
+            .parse()
+            .unwrap_or(0)
+    };
+    let entry_day = |l: &str| {
+        l.trim_start()
+            .trim_start_matches("entry(")
+            .split(',')
+            .next()
+            .and_then(|n| n.parse().ok())
+            .unwrap_or(0)
+    };
+
+    insert_sorted(&mut lines, "mod day_", "use ", format!("mod {module};"), mod_day)?;
+    insert_sorted(
+        &mut lines,
+        "    entry(",
+        "pub const DAYS",
+        format!("    entry({day}, {module}::solver),"),
+        entry_day,
+    )?;
+
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Adds `day` to the registry in `src/days/mod.rs`.
+fn register(day: u32, module: &str) -> Result<(), String> {
+    let path = Path::new(DAYS_DIR).join("mod.rs");
+    let text = std::fs::read_to_string(&path).map_err(|e| format!("{}: {e}", path.display()))?;
+    let text = registered(&text, day, module).map_err(|e| format!("{}: {e}", path.display()))?;
+    std::fs::write(&path, text).map_err(|e| format!("{}: {e}", path.display()))
+}
+
+fn create(path: &Path, contents: &str) -> Result<(), String> {
+    let write = || {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(path, contents)
+    };
+    write().map_err(|e| format!("{}: {e}", path.display()))?;
+
+    println!("created {}", path.display());
+    Ok(())
+}
+
+/// Writes `src/days/day_NN.rs` from a template, registers it, and creates an empty first example
+/// and an answers file to record its answers in. Run from the repository root.
+pub fn new(day: u32, title: &str, layout: Layout) -> Result<(), String> {
+    let module = format!("day_{day:02}");
+    let solver_path = Path::new(DAYS_DIR).join(format!("{module}.rs"));
+    if solver_path.exists() {
+        return Err(format!("{} already exists", solver_path.display()));
+    }
+
+    let id = PuzzleId::new(days::YEAR, day);
+    let example_path = Source::Example(1).path(id).expect("examples are files");
+    let answers_path = Answers::path(id);
+
+    let template = match layout {
+        Layout::Lines => LINES_TEMPLATE,
+        Layout::Grid => GRID_TEMPLATE,
+        Layout::Paragraphs => PARAGRAPHS_TEMPLATE,
+    };
+    let solver = template
+        .replace("{year}", &id.year.to_string())
+        .replace("{day}", &id.day.to_string())
+        // Debug formatting quotes and escapes the title as a Rust string literal
+        .replace("{title}", &format!("{title:?}"));
+    create(&solver_path, &solver)?;
+
+    register(day, &module)?;
+    println!("registered {module} in {DAYS_DIR}/mod.rs");
+
+    if !example_path.exists() {
+        create(&example_path, "")?;
+    }
+    if !answers_path.exists() {
+        create(&answers_path, "[example-1]\n")?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REGISTRY: &str = "\
+use crate::puzzle::Solver;
+
+mod day_01;
+mod day_07;
+mod day_07b;
+mod day_09;
+
+pub const DAYS: &[Entry] = &[
+    entry(1, day_01::solver),
+    entry(7, day_07::solver),
+    entry(9, day_09::solver),
+];
+";
+
+    #[test]
+    fn inserts_in_the_middle() {
+        let text = registered(REGISTRY, 8, "day_08").unwrap();
+        assert!(text.contains("mod day_07b;\nmod day_08;\nmod day_09;\n"), "{text}");
+        assert!(text.contains("entry(7, day_07::solver),\n    entry(8, day_08::solver),\n    entry(9"), "{text}");
+    }
+
+    #[test]
+    fn variants_sort_with_their_day() {
+        let text = registered(REGISTRY, 5, "day_05").unwrap();
+        assert!(text.contains("mod day_01;\nmod day_05;\nmod day_07;\nmod day_07b;\n"), "{text}");
+
+        // a second variant of day 7 goes after the first one
+        let text = registered(REGISTRY, 7, "day_07c").unwrap();
+        assert!(text.contains("mod day_07b;\nmod day_07c;\nmod day_09;\n"), "{text}");
+    }
+
+    #[test]
+    fn inserts_at_the_end() {
+        let text = registered(REGISTRY, 25, "day_25").unwrap();
+        assert!(text.contains("mod day_09;\nmod day_25;\n\npub const"), "{text}");
+        assert!(text.contains("entry(9, day_09::solver),\n    entry(25, day_25::solver),\n];\n"), "{text}");
+    }
+
+    #[test]
+    fn starts_an_empty_registry() {
+        let empty = "use crate::puzzle::Solver;\n\npub const DAYS: &[Entry] = &[\n];\n";
+        let text = registered(empty, 1, "day_01").unwrap();
+        assert_eq!(
+            text,
+            "use crate::puzzle::Solver;\nmod day_01;\n\npub const DAYS: &[Entry] = &[\n    entry(1, day_01::solver),\n];\n",
+        );
+
+        assert!(registered("mod day_01;\n", 2, "day_02").is_err());
+    }
+
+    #[test]
+    fn registers_in_the_real_registry() {
+        let text = registered(include_str!("../../days/mod.rs"), 23, "day_23").unwrap();
+        assert!(text.contains("mod day_22;\nmod day_23;\n"), "{text}");
+        assert!(text.contains("entry(22, day_22::solver),\n    entry(23, day_23::solver),"), "{text}");
+    }
+}
@@ -0,0 +1,124 @@
+use std::path::PathBuf;
+
+use crate::error::{Error, Result};
+
+/// Overrides where the config file is read from.
+pub const CONFIG_VAR: &str = "AOC_CONFIG";
+
+const DEFAULT_CONFIG: &str = "aoc.toml";
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Local settings for talking to the Advent of Code site, stored in `aoc.toml` (or
+/// `$AOC_CONFIG`). The file holds a session token, so it shouldn't be committed.
+///
+/// ```toml
+/// # the `session` cookie from a logged-in browser
+/// session = "53616c7465645f5f..."
+///
+/// # optional, e.g. to point at a local stub server
+/// base_url = "http://localhost:8000"
+/// ```
+///
+/// Only `key = "string"` lines and comments are understood.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    pub session: Option<String>,
+    pub base_url: String,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            session: None,
+            base_url: DEFAULT_BASE_URL.to_string(),
+        }
+    }
+}
+
+impl Config {
+    pub fn path() -> PathBuf {
+        std::env::var_os(CONFIG_VAR)
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from(DEFAULT_CONFIG))
+    }
+
+    /// Loads the config file. A missing file is the same as an empty one.
+    pub fn load() -> Result<Self> {
+        let path = Self::path();
+        match std::fs::read_to_string(&path) {
+            Ok(text) => Self::parse(&text),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(Error::io(path.display().to_string(), e)),
+        }
+    }
+
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut config = Self::default();
+
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let at_line = |e: Error| e.at_line(i + 1);
+
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| at_line(Error::parse("`<key> = \"<value>\"`", line)))?;
+            let value = value
+                .trim()
+                .strip_prefix('"')
+                .and_then(|v| v.strip_suffix('"'))
+                .ok_or_else(|| at_line(Error::parse("a quoted string", value.trim())))?
+                .replace("\\\"", "\"")
+                .replace("\\\\", "\\");
+
+            match key.trim() {
+                "session" => config.session = Some(value),
+                "base_url" => config.base_url = value,
+                other => return Err(at_line(Error::parse("`session` or `base_url`", other))),
+            }
+        }
+
+        Ok(config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_quoted_values_and_skips_comments() {
+        let text = "\
+# the session cookie
+session = \"abc\\\"123\\\\\"
+
+  # indented comment
+base_url=\"http://localhost:8000\"
+";
+        let config = Config::parse(text).unwrap();
+        assert_eq!(config.session.as_deref(), Some("abc\"123\\"));
+        assert_eq!(config.base_url, "http://localhost:8000");
+    }
+
+    #[test]
+    fn missing_keys_keep_their_defaults() {
+        assert_eq!(Config::parse("# nothing yet\n").unwrap(), Config::default());
+        assert_eq!(Config::parse("session = \"x\"").unwrap().base_url, DEFAULT_BASE_URL);
+    }
+
+    #[test]
+    fn reports_the_line_of_bad_entries() {
+        let error_at = |text| match Config::parse(text) {
+            Err(Error::Parse { expected, line, .. }) => (expected, line),
+            other => panic!("expected a parse error, got {other:?}"),
+        };
+
+        assert_eq!(error_at("# ok\nsession abc\n"), ("`<key> = \"<value>\"`".to_string(), Some(2)));
+        assert_eq!(error_at("session = abc\n"), ("a quoted string".to_string(), Some(1)));
+        assert_eq!(error_at("session = \"abc\n"), ("a quoted string".to_string(), Some(1)));
+        assert_eq!(error_at("\n\ntoken = \"abc\"\n"), ("`session` or `base_url`".to_string(), Some(3)));
+    }
+}
@@ -0,0 +1,205 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Where an iterated sequence `x0, f(x0), f(f(x0)), ...` starts repeating.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Cycle {
+    /// Steps before the first state that is part of the loop.
+    pub start: usize,
+    /// Length of the loop.
+    pub period: usize,
+}
+
+impl Cycle {
+    /// The smallest step that lands on the same state as step `n`.
+    pub fn equivalent_step(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.period
+        }
+    }
+}
+
+/// Floyd's tortoise and hare. Only needs equality, and keeps three states in memory, but calls
+/// `f` about three times as often as [`detect`].
+pub fn floyd<S, F>(x0: S, mut f: F) -> Cycle
+where
+    S: Clone + Eq,
+    F: FnMut(&S) -> S,
+{
+    // Find a meeting point inside the loop, some multiple of the period from the start.
+    let mut tortoise = f(&x0);
+    let mut hare = f(&tortoise);
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        hare = f(&hare);
+    }
+
+    // The loop starts as far from the meeting point as x0 is from the loop.
+    let mut start = 0;
+    tortoise = x0;
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        start += 1;
+    }
+
+    let mut period = 1;
+    hare = f(&tortoise);
+    while tortoise != hare {
+        hare = f(&hare);
+        period += 1;
+    }
+
+    Cycle { start, period }
+}
+
+/// Brent's algorithm. Like [`floyd`], but finds the period first using powers of two and makes
+/// fewer calls to `f`.
+pub fn brent<S, F>(x0: S, mut f: F) -> Cycle
+where
+    S: Clone + Eq,
+    F: FnMut(&S) -> S,
+{
+    let mut power = 1;
+    let mut period = 1;
+    let mut tortoise = x0.clone();
+    let mut hare = f(&x0);
+    while tortoise != hare {
+        if power == period {
+            tortoise = hare.clone();
+            power *= 2;
+            period = 0;
+        }
+        hare = f(&hare);
+        period += 1;
+    }
+
+    // Start the hare one period ahead, then walk both until they meet at the loop's start.
+    tortoise = x0.clone();
+    hare = x0;
+    for _ in 0..period {
+        hare = f(&hare);
+    }
+
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        start += 1;
+    }
+
+    Cycle { start, period }
+}
+
+/// Remembers every state until one repeats. Calls `f` exactly `start + period` times, which
+/// suits expensive steps over states that are cheap to hash.
+pub fn detect<S, F>(x0: S, f: F) -> Cycle
+where
+    S: Clone + Hash + Eq,
+    F: FnMut(&S) -> S,
+{
+    history_until(x0, f, None).0.expect("unbounded history always ends in a cycle")
+}
+
+/// The state after `n` applications of `f` to `x0`, skipping whole periods once the sequence
+/// starts repeating, so `n` can be far larger than anything that could be simulated.
+pub fn nth<S, F>(x0: S, f: F, n: usize) -> S
+where
+    S: Clone + Hash + Eq,
+    F: FnMut(&S) -> S,
+{
+    let (cycle, mut states) = history_until(x0, f, Some(n));
+    let step = cycle.map_or(n, |c| c.equivalent_step(n));
+    states.swap_remove(step)
+}
+
+/// Every state up to the first repeat, or up to step `limit` if that comes first.
+fn history_until<S, F>(x0: S, mut f: F, limit: Option<usize>) -> (Option<Cycle>, Vec<S>)
+where
+    S: Clone + Hash + Eq,
+    F: FnMut(&S) -> S,
+{
+    let mut seen = HashMap::from([(x0.clone(), 0)]);
+    let mut states = vec![x0];
+
+    while limit.is_none_or(|n| states.len() <= n) {
+        let next = f(states.last().unwrap());
+        if let Some(&start) = seen.get(&next) {
+            let period = states.len() - start;
+            return (Some(Cycle { start, period }), states);
+        }
+
+        seen.insert(next.clone(), states.len());
+        states.push(next);
+    }
+
+    (None, states)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 0, 1, 2, 3, 4, 5, 6, then 3, 4, 5, 6, ... again: a tail of 3 and a period of 4.
+    fn rho(&x: &u32) -> u32 {
+        if x == 6 { 3 } else { x + 1 }
+    }
+
+    /// Counts to 4 and wraps, so the loop starts straight away.
+    fn ring(&x: &u32) -> u32 {
+        (x + 1) % 5
+    }
+
+    #[test]
+    fn finds_the_tail_and_period() {
+        let expected = Cycle { start: 3, period: 4 };
+        assert_eq!(floyd(0, rho), expected);
+        assert_eq!(brent(0, rho), expected);
+        assert_eq!(detect(0, rho), expected);
+    }
+
+    #[test]
+    fn finds_a_cycle_at_the_start() {
+        let expected = Cycle { start: 0, period: 5 };
+        assert_eq!(floyd(0, ring), expected);
+        assert_eq!(brent(0, ring), expected);
+        assert_eq!(detect(0, ring), expected);
+
+        // a fixed point loops with period 1
+        let fixed = Cycle { start: 0, period: 1 };
+        assert_eq!(floyd(7, |&x| x), fixed);
+        assert_eq!(brent(7, |&x| x), fixed);
+        assert_eq!(detect(7, |&x| x), fixed);
+    }
+
+    #[test]
+    fn equivalent_steps() {
+        let cycle = Cycle { start: 3, period: 4 };
+        assert_eq!(cycle.equivalent_step(2), 2);
+        assert_eq!(cycle.equivalent_step(3), 3);
+        assert_eq!(cycle.equivalent_step(7), 3);
+        assert_eq!(cycle.equivalent_step(10), 6);
+    }
+
+    #[test]
+    fn nth_skips_whole_periods() {
+        // before the sequence repeats, and while it is still being simulated
+        assert_eq!(nth(0, rho, 0), 0);
+        assert_eq!(nth(0, rho, 5), 5);
+        // past the cycle
+        assert_eq!(nth(0, rho, 7), 3);
+        assert_eq!(nth(0, rho, 1_000_000_000), 3 + (1_000_000_000 - 3) % 4);
+        assert_eq!(nth(0, ring, 1_000_000_001), 1);
+
+        let mut calls = 0;
+        let counted = |x: &u32| {
+            calls += 1;
+            rho(x)
+        };
+        nth(0, counted, usize::MAX);
+        assert_eq!(calls, 7);
+    }
+}
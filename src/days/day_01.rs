@@ -0,0 +1,67 @@
+use crate::error::{Error, Result};
+use crate::puzzle::{Puzzle, PuzzleId, Solver};
+use crate::util::map_lines;
+
+use regex::Regex;
+
+fn one_a(data: &Vec<String>) -> Result<i32> {
+    let re = Regex::new(r"([0-9])").unwrap();
+
+    let values = map_lines(data, |line| {
+        let mut nums = vec![];
+        for (_, [n]) in re.captures_iter(line).map(|c| c.extract()) {
+            nums.push(n.parse::<i32>().unwrap_or(0));
+        }
+
+        match (nums.first(), nums.last()) {
+            (Some(first), Some(last)) => Ok(first * 10 + last),
+            _ => Err(Error::missing("a digit")),
+        }
+    })?;
+
+    Ok(values.iter().sum())
+}
+
+fn to_num(s: &str) -> i32 {
+    if let Ok(i) = s.parse::<i32>() {
+        i
+    } else {
+        match s {
+            "one" => 1,
+            "two" => 2,
+            "three" => 3,
+            "four" => 4,
+            "five" => 5,
+            "six" => 6,
+            "seven" => 7,
+            "eight" => 8,
+            "nine" => 9,
+            _ => 0,
+        }
+    }
+}
+
+fn one_b(data: &Vec<String>) -> Result<i32> {
+    let first_re = Regex::new(r"(one|two|three|four|five|six|seven|eight|nine|[0-9])").unwrap();
+    let last_re = Regex::new(r".*(one|two|three|four|five|six|seven|eight|nine|[0-9]).*").unwrap();
+
+    let values = map_lines(data, |line| {
+        // Either both match or neither does.
+        let (Some(f), Some(l)) = (first_re.captures(line), last_re.captures(line)) else {
+            return Err(Error::missing("a digit or a spelled-out digit"));
+        };
+        Ok(to_num(&f[1]) * 10 + to_num(&l[1]))
+    })?;
+
+    Ok(values.iter().sum())
+}
+
+pub fn solver() -> Box<dyn Solver> {
+    Box::new(Puzzle {
+        id: PuzzleId::new(2023, 1),
+        title: "Trebuchet?!",
+        parts: vec![one_a, one_b],
+        delimiter: '\n',
+        preprocess: Ok,
+    })
+}
@@ -0,0 +1,88 @@
+use crate::error::{Error, Result};
+use crate::parse::FromLine;
+use crate::puzzle::{Puzzle, PuzzleId, Solver};
+use crate::util::{map_lines, parse};
+use crate::line_format;
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+struct Game {
+    n: i32,
+    draws: Draws,
+}
+
+line_format! {
+    Game { n, draws },
+    "`Game <n>: <draws>`",
+    r"Game (?<n>[0-9]+): (?<draws>.*)",
+}
+
+struct Draws(Vec<HashMap<String, i32>>); // color -> count
+
+/// Parses `3 blue, 4 red; 1 red, 2 green`.
+impl FromStr for Draws {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        s.split(';')
+            .map(|draw| {
+                draw.split(',')
+                    .map(|balls| {
+                        let balls = balls.trim();
+                        let (n, color) = balls
+                            .split_once(' ')
+                            .ok_or_else(|| Error::parse("`<n> <color>`", balls))?;
+                        Ok((color.to_string(), parse(n)?))
+                    })
+                    .collect()
+            })
+            .collect::<Result<_>>()
+            .map(Draws)
+    }
+}
+
+fn two_a(data: &Vec<Game>) -> i32 {
+    let max: HashMap<String, i32> = HashMap::from([
+        ("red".to_string(), 12),
+        ("green".to_string(), 13),
+        ("blue".to_string(), 14),
+    ]);
+
+    data.iter()
+        .filter(|g| {
+            g.draws.0
+                .iter()
+                .all(|d| d.iter().all(|(color, n)| n <= &max[color]))
+        })
+        .map(|g| g.n)
+        .sum()
+}
+
+fn two_b(data: &Vec<Game>) -> i32 {
+    data.iter()
+        .map(|g| {
+            let mut highest = HashMap::new();
+            for draw in g.draws.0.iter() {
+                for (color, n) in draw.iter() {
+                    highest
+                        .entry(color)
+                        .and_modify(|x| *x = std::cmp::max(*x, *n))
+                        .or_insert(*n);
+                }
+            }
+            highest
+        })
+        .map(|highest| highest.iter().fold(1, |acc, (_, n)| acc * n))
+        .sum()
+}
+
+pub fn solver() -> Box<dyn Solver> {
+    Box::new(Puzzle {
+        id: PuzzleId::new(2023, 2),
+        title: "Cube Conundrum",
+        parts: vec![two_a, two_b],
+        delimiter: '\n',
+        preprocess: |text| map_lines(&text, Game::from_line),
+    })
+}
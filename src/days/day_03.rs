@@ -1,6 +1,6 @@
 use std::collections::HashSet;
 
-use advent_2023::puzzle::Puzzle;
+use crate::puzzle::{Puzzle, PuzzleId, Solver};
 
 struct Engine {
     pub data: Vec<Vec<char>>,
@@ -31,7 +31,7 @@ impl Engine {
 
     pub fn is_symbol(&self, x: i32, y: i32) -> bool {
         self.get(x, y)
-            .map_or(false, |c| !c.is_ascii_digit() && c != '.')
+            .is_some_and(|c| !c.is_ascii_digit() && c != '.')
     }
 
     pub fn try_get_digit(&self, x: i32, y: i32) -> Option<u32> {
@@ -128,12 +128,12 @@ fn b(engine: &Engine) -> i32 {
     total as i32
 }
 
-fn main() {
-    Puzzle {
-        name: "3",
+pub fn solver() -> Box<dyn Solver> {
+    Box::new(Puzzle {
+        id: PuzzleId::new(2023, 3),
+        title: "Gear Ratios",
         parts: vec![a, b],
         delimiter: '\n',
-        preprocess: |text| Engine::new(text.into_iter().map(|line| line.chars().collect()).collect()),
-    }
-    .solve();
+        preprocess: |text| Ok(Engine::new(text.into_iter().map(|line| line.chars().collect()).collect())),
+    })
 }
@@ -0,0 +1,83 @@
+use std::collections::HashSet;
+
+use crate::error::Error;
+use crate::puzzle::{Puzzle, PuzzleId, Solver};
+use crate::util::{map_lines, split_parse};
+
+#[derive(Default)]
+struct Card {
+    // winning numbers
+    pub left: HashSet<u32>,
+
+    // the ones we have
+    pub right: HashSet<u32>,
+}
+
+impl Card {
+    pub fn win_count(&self) -> i32 {
+        self.left.intersection(&self.right).count() as i32
+    }
+
+    pub fn score(n: i32) -> i32 {
+        2i32.pow((n - 1) as u32)
+    }
+}
+
+fn a(cards: &Vec<Card>) -> i32 {
+    cards
+        .iter()
+        .map(Card::win_count)
+        .filter(|size| *size > 0)
+        .map(Card::score)
+        .sum()
+}
+
+fn b(cards: &Vec<Card>) -> i32 {
+    let winning_count: Vec<_> = cards.iter().map(Card::win_count).collect();
+
+    let mut card_counts = vec![1; cards.len()];
+
+    for i in 0..cards.len() {
+        let copies = winning_count[i];
+        if copies == 0 {
+            continue;
+        }
+
+        for j in 0..copies {
+            card_counts[i + (j as usize) + 1] += card_counts[i];
+        }
+    }
+
+    card_counts.iter().sum()
+}
+
+pub fn solver() -> Box<dyn Solver> {
+    Box::new(Puzzle {
+        id: PuzzleId::new(2023, 4),
+        title: "Scratchcards",
+        parts: vec![a, b],
+        delimiter: '\n',
+        preprocess: |text| {
+            map_lines(&text, |line| {
+                // Remove "Card n:"
+                let (_, nums) = line
+                    .split_once(": ")
+                    .ok_or_else(|| Error::parse("`Card <n>: <numbers>`", line))?;
+
+                // Split on |
+                let (left, right) = nums
+                    .split_once(" | ")
+                    .ok_or_else(|| Error::parse("`<winning> | <ours>`", nums))?;
+
+                // Parse nums, keeping error columns relative to the whole line
+                let left_col = line.len() - nums.len() + 1;
+                let right_col = left_col + left.len() + " | ".len();
+
+                Ok(Card {
+                    left: split_parse(left).map_err(|e| e.at_column(left_col))?.into_iter().collect(),
+                    right: split_parse(right).map_err(|e| e.at_column(right_col))?.into_iter().collect(),
+                })
+            })
+        },
+    })
+}
@@ -1,5 +1,7 @@
-use advent_2023::puzzle::Puzzle;
-use advent_2023::util::split_parse;
+use crate::error::{Error, Result};
+use crate::parse::labeled;
+use crate::puzzle::{Paragraphs, Puzzle, PuzzleId, Solver};
+use crate::util::{paragraph_line, split_parse};
 
 struct Almanac {
     seeds: Vec<u64>,
@@ -46,13 +48,13 @@ struct Range {
 }
 
 impl Range {
-    fn from_str(s: &str) -> Self {
-        let parts = split_parse(s);
-        Self {
-            src: parts[1],
-            dst: parts[0],
-            length: parts[2],
-        }
+    fn from_str(s: &str) -> Result<Self> {
+        let parts = split_parse(s)?;
+        let [dst, src, length] = parts[..] else {
+            return Err(Error::parse("`<dst> <src> <length>`", s));
+        };
+
+        Ok(Self { src, dst, length })
     }
 
     fn translate(&self, n: u64) -> Option<u64> {
@@ -84,33 +86,32 @@ fn b(almanac: &Almanac) -> u64 {
     min
 }
 
-fn main() {
-    Puzzle {
-        name: "5",
+pub fn solver() -> Box<dyn Solver> {
+    Box::new(Puzzle {
+        id: PuzzleId::new(2023, 5),
+        title: "If You Give A Seed A Fertilizer",
         parts: vec![a, b],
-        delimiter: '\n',
-        preprocess: |text| {
-            // drop 'seeds:'
-            let seeds: Vec<_> = split_parse(&text[0][7..]);
+        delimiter: Paragraphs,
+        preprocess: |blocks| {
+            let seeds_line = blocks[0].first().ok_or_else(|| Error::missing("a `seeds:` line"))?;
+            let seeds = labeled(seeds_line, "seeds").map_err(|e| e.at_line(1))?;
 
             let mut maps: [Map; 7] = Default::default();
 
-            let mut map_idx = 0;
-            for line in text[2..].iter() {
-                if line.is_empty() {
-                    map_idx += 1;
-                    continue;
-                }
+            for (n, block) in blocks.iter().enumerate().skip(1) {
+                let start = paragraph_line(&blocks, n);
+                let map = maps
+                    .get_mut(n - 1)
+                    .ok_or_else(|| Error::parse("at most 7 maps", format!("map {n}")))
+                    .map_err(|e| e.at_line(start))?;
 
-                if line.chars().next().unwrap().is_ascii_alphabetic() {
-                    continue;
+                // the first line is the map's name
+                for (i, line) in block.iter().enumerate().skip(1) {
+                    map.add_range(Range::from_str(line).map_err(|e| e.at_line(start + i))?);
                 }
-
-                maps[map_idx].add_range(Range::from_str(line));
             }
 
-            Almanac { seeds, maps }
+            Ok(Almanac { seeds, maps })
         },
-    }
-    .solve();
+    })
 }
@@ -0,0 +1,61 @@
+use std::iter::zip;
+
+use crate::error::{Error, Result};
+use crate::parse::labeled;
+use crate::puzzle::{Parts, Puzzle, PuzzleId, Solver};
+use crate::util::parse;
+
+type Race = (u64, u64);
+
+fn travel(time: u64, hold: u64) -> u64 {
+    (time - hold) * hold
+}
+
+fn a(data: &Vec<Race>) -> u64 {
+    data.iter()
+        .map(|(t, d)| (0..*t).map(|x| travel(*t, x)).filter(|x| x > d).count() as u64)
+        .product()
+}
+
+/// Returns line `n` (0-based), which should start with `label`.
+fn line<'a>(text: &'a [String], n: usize, label: &str) -> Result<&'a str> {
+    text.get(n)
+        .map(String::as_str)
+        .ok_or_else(|| Error::missing(format!("a `{label}:` line")))
+}
+
+fn races(text: Vec<String>) -> Result<Vec<Race>> {
+    let times = labeled(line(&text, 0, "Time")?, "Time").map_err(|e| e.at_line(1))?;
+    let distances = labeled(line(&text, 1, "Distance")?, "Distance").map_err(|e| e.at_line(2))?;
+
+    Ok(zip(times, distances).collect::<Vec<_>>())
+}
+
+/// Part two reads each line as a single race, ignoring the spaces between digits.
+fn single_race(text: Vec<String>) -> Result<Vec<Race>> {
+    let digits = |n: usize, label: &str| -> Result<u64> {
+        let numbers: Vec<String> = labeled(line(&text, n, label)?, label).map_err(|e| e.at_line(n + 1))?;
+        parse(&numbers.concat()).map_err(|e| e.at_column(label.len() + 2).at_line(n + 1))
+    };
+
+    Ok(vec![(digits(0, "Time")?, digits(1, "Distance")?)])
+}
+
+pub fn solver() -> Box<dyn Solver> {
+    Box::new(Parts(vec![
+        Box::new(Puzzle {
+            id: PuzzleId::new(2023, 6),
+            title: "Wait For It",
+            parts: vec![a],
+            delimiter: '\n',
+            preprocess: races,
+        }),
+        Box::new(Puzzle {
+            id: PuzzleId::new(2023, 6),
+            title: "Wait For It",
+            parts: vec![a],
+            delimiter: '\n',
+            preprocess: single_race,
+        }),
+    ]))
+}
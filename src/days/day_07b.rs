@@ -1,7 +1,8 @@
 use std::collections::HashMap;
 
-use advent_2023::puzzle::Puzzle;
-use advent_2023::util::split_to_strings;
+use crate::error::{Error, Result};
+use crate::puzzle::{Puzzle, PuzzleId, Solver};
+use crate::util::{map_lines, parse, split_to_strings};
 
 #[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, PartialOrd, Ord)]
 enum Card {
@@ -22,8 +23,8 @@ enum Card {
 }
 
 impl Card {
-    fn from_char(c: char) -> Self {
-        match c {
+    fn from_char(c: char) -> Result<Self> {
+        Ok(match c {
             '1' => Card::V1,
             '2' => Card::V2,
             '3' => Card::V3,
@@ -38,8 +39,8 @@ impl Card {
             'Q' => Card::Q,
             'K' => Card::K,
             'A' => Card::A,
-            _ => panic!("invalid card character"),
-        }
+            _ => return Err(Error::parse("a card (a digit or one of `TJQKA`)", c)),
+        })
     }
 }
 
@@ -67,17 +68,19 @@ enum HandType {
     FiveOfAKind,  // AAAAA
 }
 
-#[allow(clippy::derive_ord_xor_partial_ord)]
-#[derive(Debug, PartialEq, Eq, Ord, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone)]
 struct Hand {
     cards: Vec<Card>,
 }
 
 impl Hand {
-    fn from_str(s: &str) -> Self {
-        Self {
-            cards: s.chars().map(Card::from_char).collect::<Vec<_>>()
-        }
+    fn from_str(s: &str) -> Result<Self> {
+        let cards = s.chars()
+            .enumerate()
+            .map(|(i, c)| Card::from_char(c).map_err(|e| e.at_column(i + 1)))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self { cards })
     }
 
     fn get_type(&self) -> HandType {
@@ -132,26 +135,32 @@ impl Hand {
     }
 }
 
-impl PartialOrd for Hand {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+impl Ord for Hand {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
         let s_type = self.get_type();
         let o_type = other.get_type();
         if s_type != o_type {
-            return s_type.partial_cmp(&o_type);
+            return s_type.cmp(&o_type);
         }
 
         let zipped = self.cards.iter()
             .zip(other.cards.iter())
             .filter(|(a, b)| a != b).collect::<Vec<_>>();
 
-        if let Some((a, b)) = zipped.get(0) {
-            Some(a.cmp(b))
+        if let Some((a, b)) = zipped.first() {
+            a.cmp(b)
         } else {
-            Some(std::cmp::Ordering::Equal)
+            std::cmp::Ordering::Equal
         }
     }
 }
 
+impl PartialOrd for Hand {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 #[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd)]
 struct Player {
     cards: Hand,
@@ -164,19 +173,24 @@ fn a(data: &Vec<Player>) -> u64 {
     data.iter().enumerate().map(|(i, p)| ((i+1) as u64) * p.bid).sum()
 }
 
-fn main() {
-    Puzzle {
-        name: "7",
+pub fn solver() -> Box<dyn Solver> {
+    Box::new(Puzzle {
+        id: PuzzleId::new(2023, 7),
+        title: "Camel Cards",
         parts: vec![a],
         delimiter: '\n',
         preprocess: |text| {
-            text.iter().map(|line| {
+            map_lines(&text, |line| {
                 let parts = split_to_strings(line);
-                Player {
-                    cards: Hand::from_str(&parts[0]),
-                    bid: parts[1].parse().unwrap(),
-                }
-            }).collect()
+                let [cards, bid] = &parts[..] else {
+                    return Err(Error::parse("`<cards> <bid>`", line));
+                };
+
+                Ok(Player {
+                    cards: Hand::from_str(cards)?,
+                    bid: parse(bid).map_err(|e| e.at_column(cards.len() + 2))?,
+                })
+            })
         },
-    }.solve();
+    })
 }
@@ -0,0 +1,148 @@
+use std::collections::HashMap;
+
+use crate::error::{Error, Result};
+use crate::math;
+use crate::parse::FromLine;
+use crate::puzzle::{Paragraphs, Puzzle, PuzzleId, Solver};
+use crate::util::{map_lines_from, paragraph_line};
+use crate::{debug, line_format};
+
+#[derive(Clone)]
+struct Directions {
+    steps: Vec<char>,
+    idx: usize,
+}
+
+impl Iterator for Directions {
+    type Item = char;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let data = *self.steps.get(self.idx)?;
+        self.idx = (self.idx + 1) % self.steps.len();
+        Some(data)
+    }
+}
+
+struct Node {
+    name: String,
+    left: String,
+    right: String,
+}
+
+line_format! {
+    Node { name, left, right },
+    "`<node> = (<left>, <right>)`",
+    r"(?<name>[0-9A-Z]+) = \((?<left>[0-9A-Z]+), (?<right>[0-9A-Z]+)\)",
+}
+
+#[derive(Clone)]
+struct Graph {
+    steps: Directions,
+    map: HashMap<String, (String, String)>,
+
+    a_nodes: Vec<String>,
+}
+
+fn len(data: &Graph, starting: &String) -> u64 {
+    let mut i = 0;
+    let mut cur_node = starting;
+
+    for step in data.steps.clone() {
+        let connections = &data.map[cur_node];
+
+        cur_node = match step {
+            'L' => &connections.0,
+            'R' => &connections.1,
+            _ => unreachable!("preprocess only allows `L` and `R`"),
+        };
+
+        i += 1;
+        if cur_node.ends_with('Z') {
+            break
+        }
+    }
+
+    i
+}
+
+fn a(data: &Graph) -> Result<u64> {
+    for node in ["AAA", "ZZZ"] {
+        if !data.map.contains_key(node) {
+            return Err(Error::missing(format!("node `{node}`")));
+        }
+    }
+
+    let mut i = 0;
+    let mut cur_node = &"AAA".to_string();
+
+    for step in data.steps.clone() {
+        let connections = &data.map[cur_node];
+
+        cur_node = match step {
+            'L' => &connections.0,
+            'R' => &connections.1,
+            _ => unreachable!("preprocess only allows `L` and `R`"),
+        };
+
+        i += 1;
+        if cur_node == "ZZZ" {
+            break
+        }
+    }
+
+    Ok(i)
+}
+
+fn b(data: &Graph) -> Result<u64> {
+    // Each ghost loops back to its start after reaching its Z node, so they all line up on the
+    // first step that's a multiple of every ghost's length.
+    let x = data.a_nodes.iter().map(|n| len(data, n)).collect::<Vec<_>>();
+    debug!("{x:?}");
+
+    let steps = math::lcm_all(x)?;
+    u64::try_from(steps).map_err(|_| Error::math(format!("{steps} steps doesn't fit in a u64")))
+}
+
+pub fn solver() -> Box<dyn Solver> {
+    Box::new(Puzzle {
+        id: PuzzleId::new(2023, 8),
+        title: "Haunted Wasteland",
+        parts: vec![a, b],
+        delimiter: Paragraphs,
+        preprocess: |blocks| {
+            let [steps, nodes] = &blocks[..] else {
+                return Err(Error::missing("steps and nodes separated by a blank line"));
+            };
+
+            let steps = steps.concat().chars().collect::<Vec<_>>();
+            if let Some(i) = steps.iter().position(|c| *c != 'L' && *c != 'R') {
+                return Err(Error::parse("`L` or `R`", steps[i]).at_column(i + 1).at_line(1));
+            }
+
+            let mut map = HashMap::new();
+            let mut a_nodes = vec![];
+
+            let first = paragraph_line(&blocks, 1);
+            let nodes = map_lines_from(nodes, first, Node::from_line)?;
+            for Node { name, left, right } in &nodes {
+                if name.ends_with('A') {
+                    a_nodes.push(name.clone());
+                }
+                map.insert(name.clone(), (left.clone(), right.clone()));
+            }
+
+            // Every step has to lead somewhere.
+            for (i, Node { left, right, .. }) in nodes.iter().enumerate() {
+                if let Some(missing) = [left, right].into_iter().find(|n| !map.contains_key(*n)) {
+                    return Err(Error::missing(format!("node `{missing}`")).at_line(first + i));
+                }
+            }
+
+            Ok(Graph {
+                steps: Directions { steps, idx: 0},
+                map,
+                a_nodes,
+            })
+        },
+    })
+}
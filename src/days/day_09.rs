@@ -1,5 +1,6 @@
-use advent_2023::puzzle::Puzzle;
-use advent_2023::util::split_parse;
+use crate::error::Error;
+use crate::puzzle::{Puzzle, PuzzleId, Solver};
+use crate::util::{map_lines, split_parse};
 
 fn next(vs: &Vec<i64>) -> Vec<i64> {
     vs.windows(2).map(|v| v[1] - v[0]).collect::<Vec<i64>>()
@@ -44,14 +45,20 @@ fn b(data: &Vec<Vec<i64>>) -> i64 {
     data.iter().map(predict_prev).sum()
 }
 
-fn main() {
-    Puzzle {
-        name: "9",
+pub fn solver() -> Box<dyn Solver> {
+    Box::new(Puzzle {
+        id: PuzzleId::new(2023, 9),
+        title: "Mirage Maintenance",
         parts: vec![a, b],
         delimiter: '\n',
         preprocess: |text| {
-            text.iter()
-                .map(|line| split_parse(line)).collect()
+            map_lines(&text, |line| {
+                let values = split_parse(line)?;
+                if values.is_empty() {
+                    return Err(Error::missing("a number"));
+                }
+                Ok(values)
+            })
         },
-    }.solve();
+    })
 }
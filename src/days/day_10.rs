@@ -1,8 +1,8 @@
-use std::collections::HashSet;
-
-use advent_2023::puzzle::Puzzle;
-use advent_2023::twod::Coord;
-use advent_2023::twod::Map as InnerMap;
+use crate::error::{Error, Result};
+use crate::puzzle::{Puzzle, PuzzleId, Solver};
+use crate::twod::{Connectivity, Coord};
+use crate::twod::Map as InnerMap;
+use crate::{debug, trace};
 
 #[derive(Debug)]
 struct Map {
@@ -12,16 +12,16 @@ struct Map {
 }
 
 impl Map {
-    fn new(data: Vec<Vec<char>>) -> Self {
-        let inner = InnerMap::new(data);
+    fn new(data: Vec<Vec<char>>) -> Result<Self> {
+        let inner = InnerMap::new(data)?;
         let start = inner.find(&'S');
 
-        Self { inner, start }
+        Ok(Self { inner, start })
     }
 
-    fn empty(xmax: i32, ymax: i32) -> Self {
+    fn empty(width: usize, height: usize) -> Self {
         Self {
-            inner: InnerMap::empty(xmax, ymax, '.'),
+            inner: InnerMap::empty(width, height, '.'),
             start: Coord::new(0, 0),
         }
     }
@@ -110,7 +110,7 @@ impl Map {
                 }
             }
 
-            println!("step() error: same y but more than one tile apart");
+            debug!("step() error: same y but more than one tile apart");
             return None;
         }
 
@@ -141,17 +141,17 @@ impl Map {
                 }
             }
 
-            println!("step() error: same x but more than one tile apart");
+            debug!("step() error: same x but more than one tile apart");
             return None;
         }
 
-        println!("warning: invalid step() check");
+        debug!("warning: invalid step() check");
         None
     }
 }
 
 fn a(map: &Map) -> u64 {
-    let mut steps_map = Map::empty(map.inner.xmax, map.inner.ymax);
+    let mut steps_map = Map::empty(map.inner.width(), map.inner.height());
     steps_map.set(map.start, '0');
 
     let mut cur_char = '0';
@@ -189,67 +189,72 @@ fn incr_char(c: char) -> char {
 }
 
 fn b(map: &Map) -> u64 {
-    let mut steps_map = Map::empty(map.inner.xmax, map.inner.ymax);
-    steps_map.set(map.start, '0');
-
-    let mut set = HashSet::new();
-    set.insert(map.start);
+    // Tiles outside the loop can squeeze between pipes, so fill on a map at double resolution,
+    // where tile (x, y) sits at (2x+1, 2y+1) and the cells between tiles are gaps. The loop walls
+    // off its tiles and the gaps it passes through; everything else is open.
+    let double = |c: Coord| c * 2 + Coord::new(1, 1);
+    let mut walls = InnerMap::empty(map.inner.width() * 2 + 1, map.inner.height() * 2 + 1, false);
+    let mut connect = |from: Coord, to: Coord| {
+        walls.set(double(from), true);
+        walls.set(double(from) + (to - from), true);
+        walls.set(double(to), true);
+    };
 
     // -- first step --
     let mut currents = map.get_adjoining_steps(map.start);
     let mut prevs = currents.iter().map(|_| map.start).collect::<Vec<_>>();
-
-    // set map
-    currents.iter().for_each(|c| steps_map.set(*c, 'X'));
-    currents.iter().for_each(|c| { set.insert(*c); });
+    currents.iter().for_each(|&c| connect(map.start, c));
 
     loop {
         // process step and increment prev/currents
         let nexts = currents.iter().zip(prevs.iter())
             .map(|(cur, prev)| map.step(*prev, *cur).unwrap())
             .collect::<Vec<_>>();
+        currents.iter().zip(nexts.iter()).for_each(|(&cur, &next)| connect(cur, next));
         prevs = currents;
         currents = nexts;
 
-        // update map and set
-        currents.iter().for_each(|c| { set.insert(*c); });
-        currents.iter().for_each(|c| steps_map.set(*c, 'X'));
-
         // check if all the same
         if currents.iter().all(|x| *x == currents[0]) {
             break;
         }
     }
 
-    println!("{}", steps_map.inner);
+    // The border ring is all gaps, so (0, 0) is outside.
+    let mut outside = InnerMap::empty(walls.width(), walls.height(), false);
+    for c in walls.flood_fill(Coord::new(0, 0), Connectivity::Four, |&wall| !wall) {
+        outside.set(c, true);
+    }
 
+    let mut steps_map = Map::empty(map.inner.width(), map.inner.height());
     let mut inside = 0;
-    for (y, line) in steps_map.inner.data.clone().iter().enumerate() {
-        for (x, &c) in line.iter().enumerate() {
-            if c == 'X' {
-                continue;
-            }
-
-            let rem = map.inner.data[y][x..].iter().filter(|&&x| x == 'X').count();
-            if rem % 2 == 1 {
-                inside += 1;
-                steps_map.set(Coord { x: x as i32, y: y as i32 }, 'I');
-            }
+    for (c, _) in map.inner.iter() {
+        if walls[double(c)] {
+            steps_map.set(c, 'X');
+        } else if !outside[double(c)] {
+            steps_map.set(c, 'I');
+            inside += 1;
         }
     }
 
-    println!("{}", steps_map.inner);
+    trace!("{}", steps_map.inner);
 
     inside
 }
 
-fn main() {
-    Puzzle {
-        name: "10",
+pub fn solver() -> Box<dyn Solver> {
+    Box::new(Puzzle {
+        id: PuzzleId::new(2023, 10),
+        title: "Pipe Maze",
         parts: vec![a, b],
         delimiter: '\n',
         preprocess: |text| {
-            Map::new(text.into_iter().map(|line| line.chars().collect()).collect())
+            let map = Map::new(text.into_iter().map(|line| line.chars().collect()).collect())?;
+            if map.start == Coord::new(-1, -1) {
+                return Err(Error::missing("a start tile `S`"));
+            }
+
+            Ok(map)
         },
-    }.solve();
+    })
 }
@@ -1,22 +1,18 @@
 use std::collections::HashSet;
 
-use advent_2023::puzzle::Puzzle;
-use advent_2023::twod::Coord;
+use crate::error::Error;
+use crate::puzzle::{Puzzle, PuzzleId, Solver};
+use crate::twod::Coord;
 
 fn pairwise_distance(data: &Vec<Coord>) -> u64 {
     let mut sum = 0;
     for i in 0..data.len() {
         for j in i..data.len() {
-            let g1 = data[i];
-            let g2 = data[j];
-
-            let x = (g1.x as i64) - (g2.x as i64);
-            let y = (g1.y as i64) - (g2.y as i64);
-            sum += x.abs() + y.abs();
+            sum += data[i].manhattan(data[j]) as u64;
         }
     }
 
-    sum as u64
+    sum
 }
 
 fn expand(mut galaxies: Vec<Coord>, expansion_factor: i32) -> Vec<Coord> {
@@ -65,9 +61,10 @@ fn b(data: &Vec<Coord>) -> u64 {
     pairwise_distance(&data)
 }
 
-fn main() {
-    Puzzle {
-        name: "11",
+pub fn solver() -> Box<dyn Solver> {
+    Box::new(Puzzle {
+        id: PuzzleId::new(2023, 11),
+        title: "Cosmic Expansion",
         parts: vec![a, b],
         delimiter: '\n',
         preprocess: |text| {
@@ -77,12 +74,16 @@ fn main() {
             for (y, line) in text.iter().enumerate() {
                 for (x, c) in line.chars().enumerate() {
                     if c == '#' {
-                        galaxies.push(Coord::from_usize(x, y));
+                        galaxies.push(Coord::from((x, y)));
                     }
                 }
             }
 
-            galaxies
+            if galaxies.is_empty() {
+                return Err(Error::missing("a galaxy `#`"));
+            }
+
+            Ok(galaxies)
         },
-    }.solve();
+    })
 }
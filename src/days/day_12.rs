@@ -1,4 +1,7 @@
-use advent_2023::puzzle::Puzzle;
+use crate::error::Error;
+use crate::parse::ints;
+use crate::puzzle::{Puzzle, PuzzleId, Solver};
+use crate::util::{map_lines, split_to_strings};
 
 #[derive(Debug)]
 struct RowRecord {
@@ -80,25 +83,27 @@ fn b(_data: &Vec<RowRecord>) -> usize {
     0
 }
 
-fn main() {
-    Puzzle {
-        name: "12",
+pub fn solver() -> Box<dyn Solver> {
+    Box::new(Puzzle {
+        id: PuzzleId::new(2023, 12),
+        title: "Hot Springs",
         parts: vec![a, b],
         delimiter: '\n',
         preprocess: |text| {
-            text.iter().map(|line| {
-                let parts = advent_2023::util::split_to_strings(line);
-                let springs = parts[0].chars().collect();
-                let counts = split_commas(&parts[1]);
+            map_lines(&text, |line| {
+                let parts = split_to_strings(line);
+                let [springs, counts] = &parts[..] else {
+                    return Err(Error::parse("`<springs> <counts>`", line));
+                };
 
-                RowRecord { springs, counts }
-            }).collect::<Vec<_>>()
-        },
-    }.solve();
-}
+                if let Some(i) = springs.find(|c| !".#?".contains(c)) {
+                    return Err(Error::parse("one of `.#?`", &springs[i..=i]).at_column(i + 1));
+                }
+
+                let counts = ints(counts).map_err(|e| e.at_column(springs.len() + 2))?;
 
-pub fn split_commas(s: &str) -> Vec<u32> {
-    s.split(',')
-        .map(|s| s.parse::<_>().unwrap())
-        .collect()
+                Ok(RowRecord { springs: springs.chars().collect(), counts })
+            })
+        },
+    })
 }
@@ -0,0 +1,72 @@
+use crate::puzzle::{Paragraphs, Puzzle, PuzzleId, Solver};
+use crate::twod::{Map, char_map_from_strings};
+use crate::util::paragraph_line;
+
+fn count_eq_mistakes<T>(vec_a: &[T], vec_b: &[T]) -> usize
+where
+    T: PartialEq + Eq
+{
+    vec_a.iter()
+        .zip(vec_b.iter())
+        .filter(|(a, b)| a != b)
+        .count()
+}
+
+/// Checks for a line of reflection between rows `y - 1` and `y`, where the rows that overlap
+/// differ in exactly `mistakes_allowed` cells.
+fn check_horiz_reflect_subset(pattern: &Map<char>, y: usize, mistakes_allowed: usize) -> bool {
+    let ymax = pattern.height();
+    if y == 0 || y >= ymax {
+        panic!("invalid reflection line: {y} vs {ymax}");
+    }
+
+    let size = std::cmp::min(y, ymax - y);
+
+    (0..size)
+        .map(|i| count_eq_mistakes(pattern.row(y - 1 - i), pattern.row(y + i)))
+        .sum::<usize>() == mistakes_allowed
+}
+
+/// The number of rows above the line of reflection, or 0 if there isn't one.
+fn find_horiz_reflect(pattern: &Map<char>, mistakes_allowed: usize) -> usize {
+    (1..pattern.height())
+        .find(|&y| check_horiz_reflect_subset(pattern, y, mistakes_allowed))
+        .unwrap_or(0)
+}
+
+fn score(pattern: &Map<char>, mistakes_allowed: usize) -> usize {
+    // A vertical line of reflection is a horizontal one in the transposed pattern.
+    find_horiz_reflect(&pattern.transpose(), mistakes_allowed)
+        + find_horiz_reflect(pattern, mistakes_allowed) * 100
+}
+
+fn a(data: &Vec<Map<char>>) -> usize {
+    data.iter()
+        .map(|pattern| score(pattern, 0))
+        .sum()
+}
+
+fn b(data: &Vec<Map<char>>) -> usize {
+    data.iter()
+        .map(|pattern| score(pattern, 1))
+        .sum()
+}
+
+pub fn solver() -> Box<dyn Solver> {
+    Box::new(Puzzle {
+        id: PuzzleId::new(2023, 13),
+        title: "Point of Incidence",
+        parts: vec![a, b],
+        delimiter: Paragraphs,
+        preprocess: |blocks| {
+            blocks
+                .iter()
+                .enumerate()
+                .map(|(i, block)| {
+                    char_map_from_strings(block.clone())
+                        .map_err(|e| e.offset_line(paragraph_line(&blocks, i) - 1))
+                })
+                .collect()
+        },
+    })
+}
@@ -0,0 +1,48 @@
+use crate::cycle;
+use crate::puzzle::{Puzzle, PuzzleId, Solver};
+use crate::twod::{Direction, Map, char_map_from_strings};
+use crate::trace;
+
+/// Rolls every round rock (O) as far as it goes in `dir`, stopping at cube rocks (#), the edge
+/// of the platform or other rocks that have already stopped.
+fn tilt(map: &mut Map<char>, dir: Direction) {
+    map.slide(dir, |&c| c == 'O', |&c| c == '.');
+}
+
+fn north_load(map: &Map<char>) -> usize {
+    map.rows().enumerate()
+        .map(|(y, line)| { line.iter().filter(|&&c| c == 'O').count() * (map.height() - y)})
+        .sum::<usize>()
+}
+
+fn a(map: &Map<char>) -> usize {
+    let mut map = map.clone();
+    trace!("{map}");
+    tilt(&mut map, Direction::North);
+    trace!("\n{map}");
+    north_load(&map)
+}
+
+fn b(map: &Map<char>) -> usize {
+    let spin = |map: &Map<char>| {
+        let mut map = map.clone();
+        for dir in [Direction::North, Direction::West, Direction::South, Direction::East] {
+            tilt(&mut map, dir);
+        }
+        map
+    };
+
+    let map = cycle::nth(map.clone(), spin, 1_000_000_000);
+    trace!("\n{map}");
+    north_load(&map)
+}
+
+pub fn solver() -> Box<dyn Solver> {
+    Box::new(Puzzle {
+        id: PuzzleId::new(2023, 14),
+        title: "Parabolic Reflector Dish",
+        parts: vec![a, b],
+        delimiter: '\n',
+        preprocess: char_map_from_strings,
+    })
+}
@@ -0,0 +1,122 @@
+use std::collections::HashMap;
+
+use crate::error::{Error, Result};
+use crate::puzzle::{Puzzle, PuzzleId, Solver};
+use crate::util::parse;
+
+fn hash_inner(v: u64, c: char) -> u64 {
+    let v = v + (c as u64);
+    (v * 17) % 256
+}
+
+fn hash(s: &str) -> u64 {
+    s.chars().fold(0, hash_inner)
+}
+
+/// One comma-separated step: `<label>-` removes a lens, `<label>=<n>` inserts or replaces one.
+struct Step {
+    text: String,
+    label: String,
+    focal_length: Option<u64>,
+}
+
+impl Step {
+    fn parse(text: &str) -> Result<Self> {
+        let (label, focal_length) = if let Some(label) = text.strip_suffix('-') {
+            (label, None)
+        } else if let Some((label, n)) = text.split_once('=') {
+            let n = parse(n).map_err(|e| e.at_column(label.len() + 2))?;
+            (label, Some(n))
+        } else {
+            return Err(Error::parse("`<label>-` or `<label>=<n>`", text));
+        };
+
+        Ok(Step {
+            text: text.to_string(),
+            label: label.to_string(),
+            focal_length,
+        })
+    }
+}
+
+fn a(data: &Vec<Step>) -> u64 {
+    data.iter()
+        .map(|step| hash(&step.text))
+        .sum()
+}
+
+#[derive(Default, Debug, Clone)]
+struct Box(Vec<(String, u64)>);
+
+impl Box {
+    fn find(&self, s: &str) -> Option<usize> {
+        self.0.iter().position(|(box_s, _)| box_s == s)
+    }
+
+    pub fn remove(&mut self, s: &str) {
+        let _ = self.find(s)
+            .map(|i| self.0.remove(i));
+    }
+
+    pub fn update(&mut self, s: &str, n: u64) {
+        if let Some(idx) = self.find(s) {
+            self.replace(idx, n);
+        } else {
+            self.insert(s, n);
+        }
+    }
+
+    fn replace(&mut self, idx: usize, n: u64) {
+        if let Some((_, old_elem)) = self.0.get_mut(idx) {
+            *old_elem = n;
+        }
+    }
+
+    fn insert(&mut self, s: &str, n: u64) {
+        self.0.push((s.to_string(), n));
+    }
+
+    fn value(&self, box_num: u8) -> u64 {
+        self.0.iter()
+            .enumerate()
+            .map(|(i, (_, n))| (box_num as u64 + 1) * (i as u64 + 1) * n)
+            .sum()
+    }
+}
+
+fn b(data: &Vec<Step>) -> u64 {
+    let mut boxes: HashMap<u8, Box> = HashMap::new();
+    (0..=255).for_each(|i| { boxes.insert(i, Default::default()); } );
+
+    for Step { label, focal_length, .. } in data {
+        let box_n = &(hash(label) as u8);
+        match focal_length {
+            None => boxes.get_mut(box_n).unwrap().remove(label),
+            Some(n) => boxes.get_mut(box_n).unwrap().update(label, *n),
+        }
+    }
+
+    boxes.iter()
+        .map(|(&box_num, b)| b.value(box_num))
+        .sum()
+}
+
+pub fn solver() -> std::boxed::Box<dyn Solver> {
+    std::boxed::Box::new(Puzzle {
+        id: PuzzleId::new(2023, 15),
+        title: "Lens Library",
+        parts: vec![a, b],
+        delimiter: ',',
+        preprocess: |steps| {
+            let mut column = 1;
+            steps
+                .iter()
+                .map(|text| {
+                    let step = Step::parse(text).map_err(|e| e.at_column(column).at_line(1));
+                    column += text.len() + 1;
+                    step
+                })
+                .collect()
+        },
+    })
+}
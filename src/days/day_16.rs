@@ -1,7 +1,8 @@
 use std::collections::HashSet;
 
-use advent_2023::puzzle::Puzzle;
-use advent_2023::twod::{Coord, Direction, Map, char_map_from_strings};
+use crate::error::Error;
+use crate::puzzle::{Puzzle, PuzzleId, Solver};
+use crate::twod::{Coord, Direction, Map, char_map_from_strings};
 
 #[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
 struct Beam {
@@ -19,18 +20,10 @@ impl Beam {
             // on the angle of the mirror. For instance, a rightward-moving beam that encounters a
             // / mirror would continue upward in the mirror's column, while a rightward-moving beam
             // that encounters a \ mirror would continue downward from the mirror's column.
-            '/' => match self.dir {
-                Direction::North => vec![self.turn_cloned(Direction::East)],
-                Direction::East => vec![self.turn_cloned(Direction::North)],
-                Direction::South => vec![self.turn_cloned(Direction::West)],
-                Direction::West => vec![self.turn_cloned(Direction::South)],
-            },
-            '\\' => match self.dir {
-                Direction::North => vec![self.turn_cloned(Direction::West)],
-                Direction::East => vec![self.turn_cloned(Direction::South)],
-                Direction::South => vec![self.turn_cloned(Direction::East)],
-                Direction::West => vec![self.turn_cloned(Direction::North)],
-            },
+            '/' if self.dir.is_vertical() => vec![self.turn_cloned(self.dir.turn_right())],
+            '/' => vec![self.turn_cloned(self.dir.turn_left())],
+            '\\' if self.dir.is_vertical() => vec![self.turn_cloned(self.dir.turn_left())],
+            '\\' => vec![self.turn_cloned(self.dir.turn_right())],
 
             // If the beam encounters the pointy end of a splitter (| or -), the beam passes
             // through the splitter as if the splitter were empty space. For instance, a
@@ -41,25 +34,15 @@ impl Beam {
             // pointing. For instance, a rightward-moving beam that encounters a | splitter would
             // split into two beams: one that continues upward from the splitter's column and one
             // that continues downward from the splitter's column.
-            '|' => match self.dir {
-                // pointy end -> empty space
-                Direction::North | Direction::South => vec![*self],
-                // flat end -> split
-                Direction::East | Direction::West => vec![
-                    self.turn_cloned(Direction::North),
-                    self.turn_cloned(Direction::South),
-                ],
-            },
-            '-' => match self.dir {
-                // flat end -> split
-                Direction::North | Direction::South => vec![
-                    self.turn_cloned(Direction::East),
-                    self.turn_cloned(Direction::West),
-                ],
-                // pointy end -> empty space
-                Direction::East | Direction::West => vec![*self],
-            },
-            _ => panic!("invalid tile {tile}"),
+            // pointy end -> empty space
+            '|' if self.dir.is_vertical() => vec![*self],
+            '-' if self.dir.is_horizontal() => vec![*self],
+            // flat end -> split
+            '|' | '-' => vec![
+                self.turn_cloned(self.dir.turn_left()),
+                self.turn_cloned(self.dir.turn_right()),
+            ],
+            _ => unreachable!("preprocess only allows `.|-/\\`, found {tile}"),
         }
     }
 
@@ -71,17 +54,12 @@ impl Beam {
     }
 
     fn step_forward(&mut self) {
-        self.coord = match self.dir {
-            Direction::North => self.coord.north(),
-            Direction::East => self.coord.east(),
-            Direction::South => self.coord.south(),
-            Direction::West => self.coord.west(),
-        }
+        self.coord = self.coord.step(self.dir);
     }
 }
 
 fn score_from_start(map: &Map<char>, start: Beam) -> usize{
-    let mut state = Map::empty(map.xmax, map.ymax, '.');
+    let mut state = Map::empty(map.width(), map.height(), '.');
     let mut beam_history = HashSet::new();
     let mut beams = vec![start];
 
@@ -117,10 +95,9 @@ fn score_from_start(map: &Map<char>, start: Beam) -> usize{
     }
 
     state
-        .data
         .iter()
-        .map(|line| line.iter().filter(|&&c| c == '#').count())
-        .sum()
+        .filter(|&(_, &c)| c == '#')
+        .count()
 }
 
 fn a(map: &Map<char>) -> usize {
@@ -134,7 +111,7 @@ fn a(map: &Map<char>) -> usize {
 fn b(map: &Map<char>) -> usize {
     [
         // top row, going south
-        (0..map.xmax)
+        (0..map.xmax())
             .map(|start_x| {
                 let start = Beam {
                     coord: Coord::new(start_x, 0),
@@ -145,10 +122,10 @@ fn b(map: &Map<char>) -> usize {
             .max().unwrap(),
 
         // bottom row, going north
-        (0..map.xmax)
+        (0..map.xmax())
             .map(|start_x| {
                 let start = Beam {
-                    coord: Coord::new(start_x, map.ymax-1),
+                    coord: Coord::new(start_x, map.ymax()-1),
                     dir: Direction::North,
                 };
                 score_from_start(map, start)
@@ -156,7 +133,7 @@ fn b(map: &Map<char>) -> usize {
             .max().unwrap(),
 
         // left column, going east
-        (0..map.ymax)
+        (0..map.ymax())
             .map(|start_y| {
                 let start = Beam {
                     coord: Coord::new(0, start_y),
@@ -167,10 +144,10 @@ fn b(map: &Map<char>) -> usize {
             .max().unwrap(),
 
         // right column, going west
-        (0..map.ymax)
+        (0..map.ymax())
             .map(|start_y| {
                 let start = Beam {
-                    coord: Coord::new(map.xmax-1, start_y),
+                    coord: Coord::new(map.xmax()-1, start_y),
                     dir: Direction::West,
                 };
                 score_from_start(map, start)
@@ -179,12 +156,21 @@ fn b(map: &Map<char>) -> usize {
     ].into_iter().max().unwrap()
 }
 
-fn main() {
-    Puzzle {
-        name: "16",
+pub fn solver() -> Box<dyn Solver> {
+    Box::new(Puzzle {
+        id: PuzzleId::new(2023, 16),
+        title: "The Floor Will Be Lava",
         parts: vec![a, b],
         delimiter: '\n',
-        preprocess: char_map_from_strings,
-    }
-    .solve();
+        preprocess: |text| {
+            let map = char_map_from_strings(text)?;
+            if let Some((c, tile)) = map.iter().find(|(_, t)| !".|-/\\".contains(**t)) {
+                return Err(Error::parse("one of `.|-/\\`", *tile)
+                    .at_column(c.x as usize + 1)
+                    .at_line(c.y as usize + 1));
+            }
+
+            Ok(map)
+        },
+    })
 }
@@ -0,0 +1,98 @@
+use crate::error::{Error, Result};
+use crate::puzzle::{Puzzle, PuzzleId, Solver};
+use crate::search;
+use crate::twod::{Coord, Direction, Map};
+use crate::util::map_lines;
+use crate::trace;
+
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+struct Beam {
+    coord: Coord,
+    dir: Direction,
+    steps_in_this_dir: u8,
+}
+
+impl Beam {
+    /// Moves the crucible can make from here, with the heat lost entering the new block. It has
+    /// to go at least `min` blocks before turning and may go at most `max` in a straight line.
+    fn get_possible_next_moves(&self, map: &Map<u32>, min: u8, max: u8) -> Vec<(Beam, u32)> {
+        let mut moves = vec![];
+
+        if self.steps_in_this_dir < max {
+            moves.push(Beam { steps_in_this_dir: self.steps_in_this_dir + 1, ..*self });
+        }
+
+        // The start hasn't moved yet, so it can set off in any direction.
+        if self.steps_in_this_dir >= min || self.steps_in_this_dir == 0 {
+            for dir in [self.dir.turn_left(), self.dir.turn_right()] {
+                moves.push(Beam { dir, steps_in_this_dir: 1, ..*self });
+            }
+        }
+
+        moves
+            .into_iter()
+            .filter_map(|mut b| {
+                b.step_forward();
+                Some((b, map.get(b.coord)?))
+            })
+            .collect()
+    }
+
+    fn step_forward(&mut self) {
+        self.coord = self.coord.step(self.dir);
+    }
+}
+
+fn min_heat_loss(map: &Map<u32>, min: u8, max: u8) -> Result<u64> {
+    // target is lower right corner
+    let target = Coord::new(map.xmax()-1, map.ymax()-1);
+
+    // Facing east, a turn covers heading south.
+    let start = Beam {
+        coord: Coord::new(0, 0),
+        dir: Direction::East,
+        steps_in_this_dir: 0,
+    };
+
+    let found = search::astar(
+        start,
+        |b| b.get_possible_next_moves(map, min, max),
+        |b| b.coord == target && b.steps_in_this_dir >= min,
+        // every block loses at least 1, since preprocess rejects 0
+        |b| b.coord.manhattan(target),
+    );
+
+    let found = found.ok_or_else(|| Error::missing(format!("a path to {target}")))?;
+    trace!("path: {}", found.path().iter().map(|b| b.coord.to_string()).collect::<Vec<_>>().join(" "));
+    Ok(found.cost as u64)
+}
+
+fn a(map: &Map<u32>) -> Result<u64> {
+    min_heat_loss(map, 1, 3)
+}
+
+fn b(map: &Map<u32>) -> Result<u64> {
+    min_heat_loss(map, 4, 10)
+}
+
+pub fn solver() -> Box<dyn Solver> {
+    Box::new(Puzzle {
+        id: PuzzleId::new(2023, 17),
+        title: "Clumsy Crucible",
+        parts: vec![a, b],
+        delimiter: '\n',
+        preprocess: |text| {
+            let data = map_lines(&text, |line| {
+                line.chars()
+                    .enumerate()
+                    .map(|(i, c)| match c.to_digit(10) {
+                        Some(d) if d > 0 => Ok(d),
+                        _ => Err(Error::parse("a digit from 1 to 9", c).at_column(i + 1)),
+                    })
+                    .collect()
+            })?;
+
+            Map::new(data)
+        },
+    })
+}
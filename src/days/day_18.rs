@@ -0,0 +1,121 @@
+use std::str::FromStr;
+
+use crate::error::{Error, Result};
+use crate::parse::FromLine;
+use crate::puzzle::{Puzzle, PuzzleId, Solver};
+use crate::twod::{Connectivity, Coord, Direction, Rect, SparseMap};
+use crate::util::map_lines;
+use crate::{line_format, trace};
+
+struct Step {
+    dir: Direction,
+    n: i32,
+    color: Color,
+}
+
+/// The step hidden in a step's colour: the first five hex digits are the distance and the last
+/// one the direction.
+struct Color {
+    dir: Direction,
+    n: i64,
+}
+
+impl FromStr for Color {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (n, dir) = s.split_at(s.len().saturating_sub(1));
+        let dir = match dir {
+            "0" => Direction::East,
+            "1" => Direction::South,
+            "2" => Direction::West,
+            "3" => Direction::North,
+            _ => return Err(Error::parse("a direction digit from 0 to 3", dir).at_column(n.len() + 1)),
+        };
+        let n = i64::from_str_radix(n, 16).map_err(|_| Error::parse("a hex distance", n))?;
+
+        Ok(Color { dir, n })
+    }
+}
+
+line_format! {
+    Step { dir, n, color },
+    "`<dir> <n> (#<color>)`",
+    r"(?<dir>[A-Z]) (?<n>[0-9]+) \(#(?<color>[a-f0-9]{6})\)",
+}
+
+/// Every corner of the trench, starting and (if it's a loop) ending at the origin.
+fn corners(steps: impl Iterator<Item = (Direction, i64)>) -> Vec<(i64, i64)> {
+    let mut corners = vec![(0, 0)];
+    for (dir, n) in steps {
+        let (x, y) = corners[corners.len() - 1];
+        corners.push((x + dir.delta().x as i64 * n, y + dir.delta().y as i64 * n));
+    }
+    corners
+}
+
+/// The cells dug out by a closed loop, trench included. The shoelace formula gives the area
+/// inside the path through the trench's cell centers, and Pick's theorem adds the part of each
+/// trench cell left outside it.
+fn lagoon_size(corners: &[(i64, i64)]) -> u64 {
+    let (twice_area, boundary) = corners
+        .windows(2)
+        .fold((0i64, 0u64), |(area, boundary), w| {
+            let ((x1, y1), (x2, y2)) = (w[0], w[1]);
+            (area + x1 * y2 - x2 * y1, boundary + x1.abs_diff(x2) + y1.abs_diff(y2))
+        });
+
+    (twice_area.unsigned_abs() + boundary) / 2 + 1
+}
+
+fn a(steps: &Vec<Step>) -> u64 {
+    let mut cur = Coord::new(0, 0);
+    let mut trench = SparseMap::new('.');
+    trench.insert(cur, '#');
+
+    for &Step { dir, n, .. } in steps {
+        for _ in 0..n {
+            cur = cur.step(dir);
+            trench.insert(cur, '#');
+        }
+    }
+
+    // Pad the trench with a ring of ground so that everything outside it is one region.
+    if let Some(Rect { min, max }) = trench.bounds() {
+        trench.insert(min - Coord::new(1, 1), '.');
+        trench.insert(max + Coord::new(1, 1), '.');
+    }
+    trace!("{trench}");
+
+    let map = trench.to_map();
+    let regions = map.regions(Connectivity::Four, |a, b| a == b);
+    let outside = regions.sizes[regions.labels[Coord::new(0, 0)]];
+
+    (map.width() * map.height() - outside) as u64
+}
+
+fn b(steps: &Vec<Step>) -> u64 {
+    lagoon_size(&corners(steps.iter().map(|s| (s.color.dir, s.color.n))))
+}
+
+pub fn solver() -> Box<dyn Solver> {
+    Box::new(Puzzle {
+        id: PuzzleId::new(2023, 18),
+        title: "Lavaduct Lagoon",
+        parts: vec![a, b],
+        delimiter: '\n',
+        preprocess: |text| {
+            let steps = map_lines(&text, Step::from_line)?;
+
+            // Both ways of reading the plan have to dig a closed loop for there to be a lagoon.
+            if corners(steps.iter().map(|s| (s.dir, s.n as i64))).last() != Some(&(0, 0)) {
+                return Err(Error::missing("a trench that ends where it starts"));
+            }
+            if corners(steps.iter().map(|s| (s.color.dir, s.color.n))).last() != Some(&(0, 0)) {
+                return Err(Error::missing("colours that describe a trench ending where it starts"));
+            }
+
+            Ok(steps)
+        },
+    })
+}
@@ -1,8 +1,13 @@
 use std::collections::HashMap;
+use std::sync::OnceLock;
 
 use regex::Regex;
 
-use advent_2023::puzzle::Puzzle;
+use crate::error::{Error, Result};
+use crate::parse::FromLine;
+use crate::puzzle::{Paragraphs, Puzzle, PuzzleId, Solver};
+use crate::util::{map_lines, map_lines_from, paragraph_line, parse};
+use crate::{line_format, trace};
 
 #[derive(Debug, Copy, Clone)]
 enum Category {
@@ -12,14 +17,16 @@ enum Category {
     S, // shiny
 }
 
-impl From<char> for Category {
-    fn from(value: char) -> Self {
+impl TryFrom<char> for Category {
+    type Error = Error;
+
+    fn try_from(value: char) -> Result<Self> {
         match value {
-            'x' => Self::X,
-            'm' => Self::M,
-            'a' => Self::A,
-            's' => Self::S,
-            _ => panic!("invalid category"),
+            'x' => Ok(Self::X),
+            'm' => Ok(Self::M),
+            'a' => Ok(Self::A),
+            's' => Ok(Self::S),
+            _ => Err(Error::parse("one of `xmas`", value)),
         }
     }
 }
@@ -32,18 +39,26 @@ struct Part {
     s: u64, // shiny
 }
 
+line_format! {
+    Part { x, m, a, s },
+    "`{x=<n>,m=<n>,a=<n>,s=<n>}`",
+    r"\{x=(?<x>[^,]*),m=(?<m>[^,]*),a=(?<a>[^,]*),s=(?<s>[^}]*)\}",
+}
+
 #[derive(Debug, Copy, Clone)]
 enum Comparison {
     Lt,
     Gt,
 }
 
-impl From<char> for Comparison {
-    fn from(value: char) -> Self {
+impl TryFrom<char> for Comparison {
+    type Error = Error;
+
+    fn try_from(value: char) -> Result<Self> {
         match value {
-            '>' => Self::Gt,
-            '<' => Self::Lt,
-            _ => panic!("invalid <> operator"),
+            '>' => Ok(Self::Gt),
+            '<' => Ok(Self::Lt),
+            _ => Err(Error::parse("`<` or `>`", value)),
         }
     }
 }
@@ -95,28 +110,40 @@ impl IfStmt {
     }
 }
 
-impl From<&String> for IfStmt {
-    fn from(value: &String) -> Self {
-        let re = Regex::new(r"([a-z]+)\{(.*+),([a-zRA]+)\}").unwrap();
-        let (_full, [name, ifs_str, terminal]) = re.captures(value).unwrap().extract();
+impl TryFrom<&str> for IfStmt {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self> {
+        static RE: OnceLock<Regex> = OnceLock::new();
+        static IF_RE: OnceLock<Regex> = OnceLock::new();
 
-        let if_re = Regex::new(r"([xmas])([<>])([0-9]+):([a-zRA]+)").unwrap();
+        let re = RE.get_or_init(|| Regex::new(r"([a-z]+)\{(.*+),([a-zRA]+)\}").unwrap());
+        let (_full, [name, ifs_str, terminal]) = re
+            .captures(value)
+            .ok_or_else(|| Error::parse("`<name>{<rules>,<default>}`", value))?
+            .extract();
+
+        let if_re = IF_RE.get_or_init(|| Regex::new(r"([xmas])([<>])([0-9]+):([a-zRA]+)").unwrap());
 
         let ifs = if_re
             .captures_iter(ifs_str)
             .map(|c| c.extract())
-            .map(|(_, [category, operator, val, then])| IfClause {
-                category: category.chars().next().unwrap().into(),
-                operator: operator.chars().next().unwrap().into(),
-                val: val.parse().unwrap(),
-                then: then.to_string(),
+            .map(|(_, [category, operator, val, then])| {
+                // the regex only matches single characters here
+                Ok(IfClause {
+                    category: category.chars().next().unwrap().try_into()?,
+                    operator: operator.chars().next().unwrap().try_into()?,
+                    val: parse(val)?,
+                    then: then.to_string(),
+                })
             })
-            .collect();
-        Self {
+            .collect::<Result<_>>()?;
+
+        Ok(Self {
             name: name.to_string(),
             ifs,
             terminal: terminal.to_string(),
-        }
+        })
     }
 }
 
@@ -227,7 +254,7 @@ fn high_low_parts(path: &Vec<IfClause>) -> (Part, Part) {
 }
 
 fn count(high: &Part, low: &Part) -> u64 {
-    println!("{} {} {} {}",
+    trace!("{} {} {} {}",
         high.x - low.x,
         high.m - low.m,
         high.a - low.a,
@@ -250,9 +277,9 @@ fn b(data: &(HashMap<String, IfStmt>, Vec<Part>)) -> u64 {
 
     accepted_paths.iter().for_each(|p| {
         let (h, l) = high_low_parts(p);
-        println!("{:?}", p);
-        println!("h: {:?}, l: {:?}", h, l);
-        println!("{}", count(&h, &l) / (4000*4000*4000*40));
+        trace!("{:?}", p);
+        trace!("h: {:?}, l: {:?}", h, l);
+        trace!("{}", count(&h, &l) / (4000*4000*4000*40));
     });
 
     high_low_for_all_paths.iter().map(|(high, low)| {
@@ -260,42 +287,41 @@ fn b(data: &(HashMap<String, IfStmt>, Vec<Part>)) -> u64 {
     }).sum()
 }
 
-fn main() {
-    Puzzle {
-        name: "19",
+pub fn solver() -> Box<dyn Solver> {
+    Box::new(Puzzle {
+        id: PuzzleId::new(2023, 19),
+        title: "Aplenty",
         parts: vec![a, b],
-        delimiter: '\n',
-        preprocess: |mut text| {
-            // split parts_list at the first non-empty line
-            let idx = text.iter().position(|x| x.is_empty()).unwrap();
-            let parts_list = text.split_off(idx + 1);
-
-            // drop newline
-            text.pop();
+        delimiter: Paragraphs,
+        preprocess: |blocks| {
+            let [workflows, parts_list] = &blocks[..] else {
+                return Err(Error::missing("workflows and a parts list separated by a blank line"));
+            };
+
+            let stmts = map_lines(workflows, |line| IfStmt::try_from(line))?;
+
+            // Every rule has to send parts somewhere, starting from `in`.
+            let names: Vec<&str> = stmts.iter().map(|s| s.name.as_str()).collect();
+            if !names.contains(&"in") {
+                return Err(Error::missing("workflow `in`"));
+            }
+            for (i, stmt) in stmts.iter().enumerate() {
+                let targets = stmt.ifs.iter().map(|c| &c.then).chain([&stmt.terminal]);
+                for target in targets {
+                    if target != "A" && target != "R" && !names.contains(&target.as_str()) {
+                        return Err(Error::missing(format!("workflow `{target}`")).at_line(i + 1));
+                    }
+                }
+            }
 
             let mut programs = HashMap::new();
-            text.iter().for_each(|line| {
-                let i: IfStmt = line.into();
+            for i in stmts {
                 programs.insert(i.name.clone(), i);
-            });
-
-            let parts = parts_list
-                .iter()
-                .map(|line| {
-                    let re =
-                        Regex::new(r"\{x=([0-9]+),m=([0-9]+),a=([0-9]+),s=([0-9]+)\}").unwrap();
-                    let captures = re.captures(line).unwrap();
-                    Part {
-                        x: captures[1].parse().unwrap(),
-                        m: captures[2].parse().unwrap(),
-                        a: captures[3].parse().unwrap(),
-                        s: captures[4].parse().unwrap(),
-                    }
-                })
-                .collect();
+            }
 
-            (programs, parts)
+            let parts = map_lines_from(parts_list, paragraph_line(&blocks, 1), Part::from_line)?;
+
+            Ok((programs, parts))
         },
-    }
-    .solve();
+    })
 }
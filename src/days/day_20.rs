@@ -2,7 +2,13 @@ use std::collections::HashMap;
 
 use regex::Regex;
 
-use advent_2023::puzzle::Puzzle;
+use crate::error::{Error, Result};
+use crate::math;
+use crate::puzzle::{Puzzle, PuzzleId, Solver};
+use crate::{debug, trace};
+
+/// How long part 2 waits for every input of the module feeding `rx` to fire.
+const MAX_PRESSES: u64 = 1 << 16;
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 enum Freq {
@@ -111,12 +117,15 @@ struct Modules {
 }
 
 impl Modules {
-    fn from_text(text: Vec<String>) -> Self {
+    fn from_text(text: Vec<String>) -> Result<Self> {
         let mut modules: HashMap<String, Box<dyn Pulse>> = HashMap::new();
         let mut dsts_map = HashMap::new();
-        for line in text.iter() {
-            let re = Regex::new(r"([&%]?)([a-z]+) -> (.*)").unwrap();
-            let (_full, [kind, name, dsts_str]) = re.captures(line).map(|c| c.extract()).unwrap();
+        let re = Regex::new(r"([&%]?)([a-z]+) -> (.*)").unwrap();
+        for (i, line) in text.iter().enumerate() {
+            let (_full, [kind, name, dsts_str]) = re
+                .captures(line)
+                .ok_or_else(|| Error::parse("`[%&]<name> -> <outputs>`", line).at_line(i + 1))?
+                .extract();
             let name = name.to_string();
             let dsts: Vec<_> = dsts_str.split(',').map(|s| s.trim().to_string()).collect();
 
@@ -124,7 +133,7 @@ impl Modules {
                 "" => modules.insert(name.clone(), Box::<Broadcaster>::default()),
                 "%" => modules.insert(name.clone(), Box::<FlipFlopModule>::default()),
                 "&" => modules.insert(name.clone(), Box::<ConjunctionModule>::default()),
-                _ => panic!("invalid module kind"),
+                _ => unreachable!("the regex only matches known module kinds"),
             };
 
             dsts_map.insert(name, dsts);
@@ -139,14 +148,14 @@ impl Modules {
             }
         }
 
-        Modules {
+        Ok(Modules {
             modules,
             dsts: dsts_map,
-        }
+        })
     }
 
     fn send_pulse(&mut self, a: Action) -> Vec<Action> {
-        //println!("{a}");
+        trace!("{a}");
         let Some(module) = &mut self.modules.get_mut(&a.target) else { return vec![] };
         if let Some(output_freq) = module.pulse(a.kind, a.from.clone()) {
             self.dsts[&a.target]
@@ -188,11 +197,11 @@ impl Modules {
     }
 }
 
-fn a(text: &Vec<String>) -> u64 {
+fn a(text: &Vec<String>) -> Result<u64> {
     // hack to get around Puzzle limitations...
     // need modules to be mutable, but we can't clone() inthe input to this fn because of trait
     // objects
-    let mut modules = Modules::from_text(text.to_vec());
+    let mut modules = Modules::from_text(text.to_vec())?;
 
     let (low, high) = (0..1000)
         .flat_map(|_| modules.push_button())
@@ -204,25 +213,59 @@ fn a(text: &Vec<String>) -> u64 {
             }
         });
 
-    println!("{low} {high}");
-    low * high
+    debug!("{low} {high}");
+    Ok(low * high)
 }
 
-fn b(text: &Vec<String>) -> u64 {
+fn b(text: &Vec<String>) -> Result<u64> {
     // hack to get around Puzzle limitations...
     // need modules to be mutable, but we can't clone() inthe input to this fn because of trait
     // objects
-    let mut _modules = Modules::from_text(text.to_vec());
+    let mut modules = Modules::from_text(text.to_vec())?;
+
+    // rx hangs off a single conjunction, which only sends it a low pulse once every one of its
+    // inputs has most recently sent a high one. Each input does that on a fixed period, so find
+    // the periods and line them up.
+    let feeds_into = |target: &str| -> Vec<String> {
+        modules.dsts.iter()
+            .filter(|(_, dsts)| dsts.iter().any(|d| d == target))
+            .map(|(name, _)| name.clone())
+            .collect()
+    };
+    let [hub] = &feeds_into("rx")[..] else {
+        return Err(Error::missing("a single module sending to `rx`"));
+    };
+    let hub = hub.clone();
+    let mut inputs = feeds_into(&hub);
+
+    let mut periods = vec![];
+    for presses in 1..=MAX_PRESSES {
+        let history = modules.push_button();
+        inputs.retain(|input| {
+            let high = history.iter().any(|a| a.target == hub && a.from == *input && a.kind == Freq::High);
+            if high {
+                debug!("{input} sends high after {presses} presses");
+                periods.push(presses);
+            }
+            !high
+        });
 
-    0
+        if inputs.is_empty() {
+            let presses = math::lcm_all(periods)?;
+            return u64::try_from(presses)
+                .map_err(|_| Error::math(format!("{presses} presses doesn't fit in a u64")));
+        }
+    }
+
+    Err(Error::missing(format!("a high pulse from each of {inputs:?} within {MAX_PRESSES} presses")))
 }
 
-fn main() {
-    Puzzle {
-        name: "20",
+pub fn solver() -> Box<dyn Solver> {
+    Box::new(Puzzle {
+        id: PuzzleId::new(2023, 20),
+        title: "Pulse Propagation",
         parts: vec![a, b],
         delimiter: '\n',
-        preprocess: |text| text,
-    }
-    .solve();
+        preprocess: Ok,
+    })
 }
@@ -0,0 +1,64 @@
+use crate::error::{Error, Result};
+use crate::puzzle::{Puzzle, PuzzleId, Solver};
+use crate::twod::{Connectivity, Coord, Map, char_map_from_strings};
+use crate::trace;
+
+const STEPS_A: u32 = 64;
+const STEPS_B: i64 = 26501365;
+
+fn start(map: &Map<char>) -> Result<Coord> {
+    let start = map.find(&'S');
+    if start == Coord::new(-1, -1) {
+        return Err(Error::missing("a start tile `S`"));
+    }
+    Ok(start)
+}
+
+fn a(map: &Map<char>) -> Result<i64> {
+    let dist = map.distances([start(map)?], Connectivity::Four, |&c| c != '#');
+    Ok(dist.count_exactly(STEPS_A) as i64)
+}
+
+fn b(map: &Map<char>) -> Result<i64> {
+    // The real input is a square with S in the middle and clear paths straight out of it, so
+    // the elf reaches each new ring of tiles on the same schedule and the count grows
+    // quadratically with every `size` steps. Sample it for the first three rings and
+    // extrapolate.
+    let size = map.width();
+    let start = start(map)?;
+    if map.height() != size || start != Coord::from((size / 2, size / 2)) {
+        return Err(Error::missing("a square map with `S` in the middle"));
+    }
+    let (x, y) = (start.x as usize, start.y as usize);
+    if map.row(y).contains(&'#') || map.column(x).any(|&c| c == '#') {
+        return Err(Error::missing("a row and column through `S` with no rocks"));
+    }
+
+    // Two tiles in every direction is enough to walk out to the third ring.
+    let tiled = map.tiled(5);
+    let center = start + Coord::from((size * 2, size * 2));
+    let dist = tiled.distances([center], Connectivity::Four, |&c| c != '#');
+
+    let rem = STEPS_B % size as i64;
+    let [f0, f1, f2] = [0, 1, 2].map(|k| dist.count_exactly((rem + k * size as i64) as u32) as i64);
+    trace!("reachable after {rem} + k * {size} steps: {f0}, {f1}, {f2}");
+
+    // Newton's forward differences for f(n) = f0 + n * (f1 - f0) + n(n-1)/2 * second difference.
+    let n = STEPS_B / size as i64;
+    let overflow = || Error::math(format!("extrapolating {n} rings of {f0}, {f1}, {f2} overflows i64"));
+    let linear = n.checked_mul(f1 - f0).ok_or_else(overflow)?;
+    let quadratic = (n * (n - 1) / 2).checked_mul(f2 + f0 - 2 * f1).ok_or_else(overflow)?;
+    f0.checked_add(linear)
+        .and_then(|t| t.checked_add(quadratic))
+        .ok_or_else(overflow)
+}
+
+pub fn solver() -> Box<dyn Solver> {
+    Box::new(Puzzle {
+        id: PuzzleId::new(2023, 21),
+        title: "Step Counter",
+        parts: vec![a, b],
+        delimiter: '\n',
+        preprocess: char_map_from_strings,
+    })
+}
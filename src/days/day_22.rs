@@ -0,0 +1,102 @@
+use std::collections::{HashSet, VecDeque};
+
+use crate::error::Error;
+use crate::parse::all_ints;
+use crate::puzzle::{Puzzle, PuzzleId, Solver};
+use crate::threed::{settle, Axis, Coord, Cuboid, Supports, Voxels};
+use crate::util::map_lines;
+use crate::trace;
+
+struct Space {
+    bricks: Vec<Cuboid>,
+    supports: Supports,
+}
+
+impl Space {
+    fn new(bricks: Vec<Cuboid>) -> Self {
+        let bricks = settle(&bricks, 1);
+        let supports = Supports::new(&bricks);
+
+        Space { bricks, supports }
+    }
+
+    /// Side view looking along `axis`, with bricks labelled A, B, C, ... in input order.
+    fn view(&self, axis: Axis) -> String {
+        let mut voxels = Voxels::new();
+        let mut name = '@'; // @ is 1 before A
+        for brick in self.bricks.iter() {
+            name = incr_char(name);
+            voxels.fill(brick, name);
+        }
+
+        voxels.render(axis)
+    }
+
+    // You can disintegrate a brick when all bricks above it are above 2+ bricks.
+    fn can_disintegrate(&self, brick: usize) -> bool {
+        self.supports.above[brick]
+            .iter()
+            .all(|&above| self.supports.below[above].len() > 1)
+    }
+
+    /// How many other bricks fall if `brick` is disintegrated.
+    fn count_supported_by(&self, brick: usize) -> usize {
+        let mut fallen = HashSet::from([brick]);
+        let mut queue = VecDeque::from([brick]);
+
+        while let Some(b) = queue.pop_front() {
+            for &above in self.supports.above[b].iter() {
+                // a brick falls once everything under it has
+                if !fallen.contains(&above)
+                    && self.supports.below[above].iter().all(|below| fallen.contains(below))
+                {
+                    fallen.insert(above);
+                    queue.push_back(above);
+                }
+            }
+        }
+
+        // remove self from set
+        fallen.len() - 1
+    }
+}
+
+fn a(space: &Space) -> usize {
+    (0..space.bricks.len())
+        .filter(|&b| space.can_disintegrate(b))
+        .count()
+}
+
+fn b(space: &Space) -> usize {
+    (0..space.bricks.len())
+        .map(|b| space.count_supported_by(b))
+        .sum()
+}
+
+pub fn solver() -> Box<dyn Solver> {
+    Box::new(Puzzle {
+        id: PuzzleId::new(2023, 22),
+        title: "Sand Slabs",
+        parts: vec![a, b],
+        delimiter: '\n',
+        preprocess: |text| {
+            let bricks = map_lines(&text, |line| {
+                let [x1, y1, z1, x2, y2, z2] = all_ints(line)?[..] else {
+                    return Err(Error::parse("`<x>,<y>,<z>~<x>,<y>,<z>`", line));
+                };
+                Ok(Cuboid::new(Coord::new(x1, y1, z1), Coord::new(x2, y2, z2)))
+            })?;
+
+            let space = Space::new(bricks);
+
+            // "from x" has a column per x, so it looks along y
+            trace!("from x:\n{}\nfrom y:\n{}", space.view(Axis::Y), space.view(Axis::X));
+
+            Ok(space)
+        },
+    })
+}
+
+fn incr_char(c: char) -> char {
+    std::char::from_u32(c as u32 + 1).unwrap_or(c)
+}
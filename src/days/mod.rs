@@ -0,0 +1,75 @@
+// Part functions take `&T` to match `Puzzle::parts`, even when T is a Vec.
+#![allow(clippy::ptr_arg)]
+
+use crate::puzzle::Solver;
+
+mod day_01;
+mod day_02;
+mod day_03;
+mod day_04;
+mod day_05;
+mod day_06;
+mod day_07;
+mod day_07b;
+mod day_08;
+mod day_09;
+mod day_10;
+mod day_11;
+mod day_12;
+mod day_13;
+mod day_14;
+mod day_15;
+mod day_16;
+mod day_17;
+mod day_18;
+mod day_19;
+mod day_20;
+mod day_21;
+mod day_22;
+
+/// The year every day in this module belongs to.
+pub const YEAR: u32 = 2023;
+
+/// The registered solver for a single day.
+pub struct Entry {
+    pub day: u32,
+    pub solver: fn() -> Box<dyn Solver>,
+}
+
+const fn entry(day: u32, solver: fn() -> Box<dyn Solver>) -> Entry {
+    Entry { day, solver }
+}
+
+pub const DAYS: &[Entry] = &[
+    entry(1, day_01::solver),
+    entry(2, day_02::solver),
+    entry(3, day_03::solver),
+    entry(4, day_04::solver),
+    entry(5, day_05::solver),
+    entry(6, day_06::solver),
+    entry(7, day_07::solver),
+    entry(8, day_08::solver),
+    entry(9, day_09::solver),
+    entry(10, day_10::solver),
+    entry(11, day_11::solver),
+    entry(12, day_12::solver),
+    entry(13, day_13::solver),
+    entry(14, day_14::solver),
+    entry(15, day_15::solver),
+    entry(16, day_16::solver),
+    entry(17, day_17::solver),
+    entry(18, day_18::solver),
+    entry(19, day_19::solver),
+    entry(20, day_20::solver),
+    entry(21, day_21::solver),
+    entry(22, day_22::solver),
+];
+
+pub fn solver_for(day: u32) -> Option<Box<dyn Solver>> {
+    DAYS.iter().find(|e| e.day == day).map(|e| (e.solver)())
+}
+
+/// Every registered day, ascending.
+pub fn registered_days() -> Vec<u32> {
+    DAYS.iter().map(|e| e.day).collect()
+}
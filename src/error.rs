@@ -0,0 +1,115 @@
+use std::fmt::Display;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Everything that can go wrong between reading an input file and printing an answer.
+///
+/// Lines and columns are 1-based. They are optional because the code that notices a bad token
+/// usually doesn't know where it came from; callers attach them on the way out with
+/// [`Error::at_line`] and [`Error::at_column`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// `found` could not be read as `expected`.
+    Parse {
+        expected: String,
+        found: String,
+        line: Option<usize>,
+        column: Option<usize>,
+    },
+
+    /// The input is missing something the puzzle needs, like a section or a start tile.
+    Missing {
+        expected: String,
+        line: Option<usize>,
+    },
+
+    /// The input file could not be read.
+    Io { path: String, message: String },
+
+    /// A calculation has no answer, like an overflow or congruences that contradict each other.
+    Math { message: String },
+}
+
+impl Error {
+    pub fn parse(expected: impl Into<String>, found: impl Into<String>) -> Self {
+        Error::Parse {
+            expected: expected.into(),
+            found: found.into(),
+            line: None,
+            column: None,
+        }
+    }
+
+    pub fn missing(expected: impl Into<String>) -> Self {
+        Error::Missing {
+            expected: expected.into(),
+            line: None,
+        }
+    }
+
+    pub fn io(path: impl Into<String>, e: std::io::Error) -> Self {
+        Error::Io {
+            path: path.into(),
+            message: e.to_string(),
+        }
+    }
+
+    pub fn math(message: impl Into<String>) -> Self {
+        Error::Math { message: message.into() }
+    }
+
+    /// Sets the line the error happened on, unless a more specific one is already known.
+    pub fn at_line(mut self, n: usize) -> Self {
+        match &mut self {
+            Error::Parse { line, .. } | Error::Missing { line, .. } => {
+                line.get_or_insert(n);
+            },
+            Error::Io { .. } | Error::Math { .. } => (),
+        }
+        self
+    }
+
+    /// Moves a known line down by `n`, for errors from a block that was parsed on its own but
+    /// starts partway into the input.
+    pub fn offset_line(mut self, n: usize) -> Self {
+        if let Error::Parse { line: Some(l), .. } | Error::Missing { line: Some(l), .. } = &mut self {
+            *l += n;
+        }
+        self
+    }
+
+    /// Sets the column the error happened at. Columns nest, so an offset found inside a token is
+    /// added to the token's own column.
+    pub fn at_column(mut self, n: usize) -> Self {
+        if let Error::Parse { column, .. } = &mut self {
+            *column = Some(column.map_or(n, |c| c + n - 1));
+        }
+        self
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parse { expected, found, line, column } => {
+                match (line, column) {
+                    (Some(l), Some(c)) => write!(f, "line {l}, column {c}: ")?,
+                    (Some(l), None) => write!(f, "line {l}: ")?,
+                    (None, Some(c)) => write!(f, "column {c}: ")?,
+                    (None, None) => (),
+                }
+                write!(f, "expected {expected}, found `{found}`")
+            },
+            Error::Missing { expected, line } => {
+                if let Some(l) = line {
+                    write!(f, "line {l}: ")?;
+                }
+                write!(f, "missing {expected}")
+            },
+            Error::Io { path, message } => write!(f, "{path}: {message}"),
+            Error::Math { message } => write!(f, "{message}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
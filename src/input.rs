@@ -0,0 +1,127 @@
+use std::io::Read;
+use std::path::PathBuf;
+
+use crate::error::{Error, Result};
+use crate::puzzle::PuzzleId;
+
+/// Overrides the directory real inputs are read from.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+const DEFAULT_INPUT_DIR: &str = "inputs";
+const EXAMPLES_DIR: &str = "examples";
+
+/// Where a day's input text comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    /// `<input dir>/<year>/<day>`, where the input dir is `$AOC_INPUT_DIR` or `inputs`.
+    Real,
+    /// `examples/<year>/<day>/<n>.txt`, one of the sample inputs from the puzzle text.
+    Example(u32),
+    /// An explicit file.
+    File(PathBuf),
+    Stdin,
+}
+
+impl Source {
+    /// The file this source reads for puzzle `id`, if it reads a file at all.
+    pub fn path(&self, id: PuzzleId) -> Option<PathBuf> {
+        match self {
+            Source::Real => Some(input_dir().join(id.path())),
+            Source::Example(n) => Some(examples_dir(id).join(format!("{n}.txt"))),
+            Source::File(path) => Some(path.clone()),
+            Source::Stdin => None,
+        }
+    }
+
+    /// Names the input in the answers file. Only real and example inputs have one, since
+    /// explicit files and stdin can change between runs.
+    pub fn label(&self) -> Option<String> {
+        match self {
+            Source::Real => Some("real".to_string()),
+            Source::Example(n) => Some(format!("example-{n}")),
+            Source::File(_) | Source::Stdin => None,
+        }
+    }
+
+    /// The inverse of [`Source::label`].
+    pub fn from_label(label: &str) -> Option<Self> {
+        if label == "real" {
+            return Some(Source::Real);
+        }
+
+        label.strip_prefix("example-")?.parse().ok().map(Source::Example)
+    }
+
+    /// Names the input in diagnostics.
+    pub fn describe(&self, id: PuzzleId) -> String {
+        match self.path(id) {
+            Some(path) => path.display().to_string(),
+            None => "<stdin>".to_string(),
+        }
+    }
+
+    pub fn read(&self, id: PuzzleId) -> Result<String> {
+        match self.path(id) {
+            Some(path) => std::fs::read_to_string(&path).map_err(|e| {
+                match self.unmigrated_path(id).filter(|old| old.is_file()) {
+                    Some(old) if e.kind() == std::io::ErrorKind::NotFound => Error::Io {
+                        path: path.display().to_string(),
+                        message: format!(
+                            "not found, but {} is; inputs are now filed by year, so move it here",
+                            old.display()
+                        ),
+                    },
+                    _ => Error::io(path.display().to_string(), e),
+                }
+            }),
+            None => {
+                let mut contents = String::new();
+                std::io::stdin()
+                    .read_to_string(&mut contents)
+                    .map_err(|e| Error::io("<stdin>", e))?;
+                Ok(contents)
+            },
+        }
+    }
+
+    /// Where this source's file was before inputs were filed by year, to point people at the new
+    /// place when only the old file exists.
+    fn unmigrated_path(&self, id: PuzzleId) -> Option<PathBuf> {
+        match self {
+            Source::Real => Some(input_dir().join(id.day.to_string())),
+            Source::Example(n) => {
+                Some(PathBuf::from(EXAMPLES_DIR).join(id.day.to_string()).join(format!("{n}.txt")))
+            },
+            Source::File(_) | Source::Stdin => None,
+        }
+    }
+}
+
+fn examples_dir(id: PuzzleId) -> PathBuf {
+    PathBuf::from(EXAMPLES_DIR).join(id.path())
+}
+
+/// The example numbers on disk for puzzle `id`, ascending.
+pub fn examples(id: PuzzleId) -> Vec<u32> {
+    let Ok(entries) = std::fs::read_dir(examples_dir(id)) else {
+        return vec![];
+    };
+
+    let mut examples: Vec<u32> = entries
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            if path.extension()? != "txt" {
+                return None;
+            }
+            path.file_stem()?.to_str()?.parse().ok()
+        })
+        .collect();
+    examples.sort();
+    examples
+}
+
+pub fn input_dir() -> PathBuf {
+    std::env::var_os(INPUT_DIR_VAR)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_INPUT_DIR))
+}
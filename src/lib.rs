@@ -1,3 +1,14 @@
+pub mod answers;
+pub mod config;
+pub mod cycle;
+pub mod days;
+pub mod error;
+pub mod input;
+pub mod log;
+pub mod math;
+pub mod parse;
+pub mod threed;
 pub mod twod;
 pub mod puzzle;
+pub mod search;
 pub mod util;
@@ -0,0 +1,57 @@
+//! Debug output for solvers. Everything goes to stderr, and nothing is printed (or even
+//! formatted) unless the runner was asked for it with `-v` (debug) or `-vv` (trace).
+//!
+//! ```ignore
+//! use crate::{debug, trace};
+//!
+//! debug!("{low} {high}");
+//! trace!("{map}");
+//! ```
+
+use std::sync::atomic::{AtomicU8, Ordering};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Off,
+    /// Summaries worth a line or two per part.
+    Debug,
+    /// Per-step state, maps, anything that can run to thousands of lines.
+    Trace,
+}
+
+static LEVEL: AtomicU8 = AtomicU8::new(Level::Off as u8);
+
+pub fn set_level(level: Level) {
+    LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+/// Maps a `-v` count onto a level: none is off, one is debug, two or more is trace.
+pub fn set_verbosity(count: u8) {
+    set_level(match count {
+        0 => Level::Off,
+        1 => Level::Debug,
+        _ => Level::Trace,
+    });
+}
+
+pub fn enabled(level: Level) -> bool {
+    level != Level::Off && level as u8 <= LEVEL.load(Ordering::Relaxed)
+}
+
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        if $crate::log::enabled($crate::log::Level::Debug) {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        if $crate::log::enabled($crate::log::Level::Trace) {
+            eprintln!($($arg)*);
+        }
+    };
+}
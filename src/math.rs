@@ -0,0 +1,157 @@
+use crate::error::{Error, Result};
+
+pub fn gcd(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Fails rather than wrapping if the result doesn't fit in a `u128`.
+pub fn lcm(a: u128, b: u128) -> Result<u128> {
+    if a == 0 || b == 0 {
+        return Ok(0);
+    }
+
+    (a / gcd(a, b))
+        .checked_mul(b)
+        .ok_or_else(|| Error::math(format!("lcm({a}, {b}) overflows u128")))
+}
+
+/// The gcd of every value, or 0 if there are none.
+pub fn gcd_all<I>(values: I) -> u128
+where
+    I: IntoIterator,
+    I::Item: Into<u128>,
+{
+    values.into_iter().fold(0, |acc, v| gcd(acc, v.into()))
+}
+
+/// The lcm of every value, or 1 if there are none.
+pub fn lcm_all<I>(values: I) -> Result<u128>
+where
+    I: IntoIterator,
+    I::Item: Into<u128>,
+{
+    values.into_iter().try_fold(1, |acc, v| lcm(acc, v.into()))
+}
+
+/// Returns `(g, x, y)` where `g = gcd(a, b)` and `a*x + b*y = g`.
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// Chinese Remainder Theorem: the smallest non-negative `x` with `x ≡ r (mod m)` for every
+/// `(r, m)`, along with the modulus it repeats at. Moduli needn't be coprime, but then the
+/// residues have to agree wherever the moduli overlap, or there is no answer.
+pub fn crt<I>(congruences: I) -> Result<(i128, i128)>
+where
+    I: IntoIterator<Item = (i128, i128)>,
+{
+    congruences.into_iter().try_fold((0, 1), |(r1, m1), (r2, m2)| {
+        if m2 <= 0 {
+            return Err(Error::math(format!("modulus must be positive, found {m2}")));
+        }
+        let r2 = r2.rem_euclid(m2);
+
+        let (g, p, _) = extended_gcd(m1, m2);
+        if (r2 - r1) % g != 0 {
+            return Err(Error::math(format!(
+                "x ≡ {r1} (mod {m1}) and x ≡ {r2} (mod {m2}) have no common solution"
+            )));
+        }
+
+        let overflow = || Error::math(format!("combining mod {m1} and mod {m2} overflows i128"));
+        let m = (m1 / g).checked_mul(m2).ok_or_else(overflow)?;
+
+        // m1 * p ≡ g (mod m2), so stepping r1 by m1 * k for the right k lands on r2.
+        let step = m2 / g;
+        let k = ((r2 - r1) / g % step)
+            .checked_mul(p % step)
+            .ok_or_else(overflow)?
+            .rem_euclid(step);
+
+        Ok(((r1 + m1 * k).rem_euclid(m), m))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_math_error<T>(result: Result<T>) -> bool {
+        matches!(result, Err(Error::Math { .. }))
+    }
+
+    #[test]
+    fn gcd_and_lcm() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(0, 5), 5);
+        assert_eq!(lcm(4, 6).unwrap(), 12);
+        assert_eq!(lcm(0, 6).unwrap(), 0);
+        assert_eq!(gcd_all([12u64, 18, 27]), 3);
+        assert_eq!(lcm_all([2u32, 3, 4]).unwrap(), 12);
+        assert_eq!(lcm_all(Vec::<u32>::new()).unwrap(), 1);
+        assert!(is_math_error(lcm(u128::MAX, u128::MAX - 1)));
+    }
+
+    #[test]
+    fn extended_gcd_satisfies_bezout() {
+        let pairs = [(240, 46), (46, 240), (17, 5), (-12, 18), (12, -18), (0, 7), (7, 0), (1 << 100, 3)];
+        for (a, b) in pairs {
+            let (g, x, y) = extended_gcd(a, b);
+            assert_eq!(a * x + b * y, g, "({a}, {b})");
+            assert_eq!(g as u128, gcd(a.unsigned_abs(), b.unsigned_abs()), "({a}, {b})");
+        }
+    }
+
+    #[test]
+    fn crt_with_coprime_moduli() {
+        assert_eq!(crt([(2, 3), (3, 5), (2, 7)]).unwrap(), (23, 105));
+        // residues are reduced first
+        assert_eq!(crt([(-1, 3), (8, 5)]).unwrap(), (8, 15));
+        assert_eq!(crt([]).unwrap(), (0, 1));
+    }
+
+    #[test]
+    fn crt_with_compatible_moduli() {
+        // 4 and 6 share a 2, and both residues are even
+        assert_eq!(crt([(2, 4), (4, 6)]).unwrap(), (10, 12));
+        assert_eq!(crt([(3, 6), (3, 6)]).unwrap(), (3, 6));
+    }
+
+    #[test]
+    fn crt_rejects_contradictions() {
+        // odd and even at once
+        assert!(is_math_error(crt([(1, 4), (2, 6)])));
+        assert!(is_math_error(crt([(1, 0)])));
+        assert!(is_math_error(crt([(1, -3)])));
+    }
+
+    #[test]
+    fn crt_near_the_overflow_edge() {
+        // the combined modulus is about 2^124, which still fits
+        let (m1, m2) = (1i128 << 62, 3i128.pow(39));
+        let (x, m) = crt([(5, m1), (7, m2)]).unwrap();
+        assert_eq!(m, m1 * m2);
+        assert_eq!((x % m1, x % m2), (5, 7));
+
+        // consecutive numbers are coprime, so the combined modulus is their product
+        assert!(is_math_error(crt([(0, i128::MAX), (1, i128::MAX - 1)])));
+    }
+}
@@ -0,0 +1,267 @@
+//! Helpers for the line formats that keep coming up in puzzle inputs. Everything returns an
+//! [`Error`] pointing at the offending column instead of panicking.
+
+use std::convert::Infallible;
+use std::num::{ParseFloatError, ParseIntError};
+use std::str::FromStr;
+
+use crate::error::{Error, Result};
+use crate::util::parse;
+
+/// Byte offset of `part` within `whole`. `part` must be a subslice of `whole`.
+fn offset(whole: &str, part: &str) -> usize {
+    part.as_ptr() as usize - whole.as_ptr() as usize
+}
+
+/// Parses each non-empty token, tagging errors with the token's column. Tokens must be subslices
+/// of `s`.
+fn parse_tokens<'a, T, I>(s: &'a str, tokens: I) -> Result<Vec<T>>
+where
+    T: FromStr,
+    I: Iterator<Item = &'a str>,
+{
+    tokens
+        .filter(|t| !t.is_empty())
+        .map(|t| parse(t).map_err(|e| e.at_column(offset(s, t) + 1)))
+        .collect()
+}
+
+/// Parses numbers separated by commas, whitespace, or both, e.g. `1,2,3`, `-4 5  6` or `7, 8`.
+pub fn ints<T>(s: &str) -> Result<Vec<T>>
+where
+    T: FromStr
+{
+    parse_tokens(s, s.split(|c: char| c == ',' || c.is_whitespace()))
+}
+
+/// Parses a `Label: 1 2 3` line, returning the numbers after the label.
+pub fn labeled<T>(line: &str, label: &str) -> Result<Vec<T>>
+where
+    T: FromStr
+{
+    let rest = line
+        .strip_prefix(label)
+        .and_then(|rest| rest.strip_prefix(':'))
+        .ok_or_else(|| Error::parse(format!("`{label}: <numbers>`"), line))?;
+
+    ints(rest).map_err(|e| e.at_column(label.len() + 2))
+}
+
+/// Pulls every integer out of a line, skipping whatever is between them. A `-` directly before
+/// digits makes the number negative, so `1,0,1~1,2,1` gives six numbers and `x=-3` gives one.
+pub fn all_ints<T>(s: &str) -> Result<Vec<T>>
+where
+    T: FromStr
+{
+    let mut tokens = vec![];
+    let mut start = None;
+
+    for (i, c) in s.char_indices() {
+        let starts_number = c.is_ascii_digit()
+            || (c == '-' && s[i + 1..].starts_with(|c: char| c.is_ascii_digit()));
+
+        match (start, c.is_ascii_digit()) {
+            (None, _) if starts_number => start = Some(i),
+            (Some(from), false) => {
+                tokens.push(&s[from..i]);
+                start = if starts_number { Some(i) } else { None };
+            },
+            _ => (),
+        }
+    }
+
+    if let Some(from) = start {
+        tokens.push(&s[from..]);
+    }
+
+    parse_tokens(s, tokens.into_iter())
+}
+
+/// Parses `key=value` pairs separated by commas, like `{x=787,m=2655}` or `a=1, b=2`. Surrounding
+/// braces are optional. Keys are borrowed from `s`, in the order they appear.
+pub fn key_values<T>(s: &str) -> Result<Vec<(&str, T)>>
+where
+    T: FromStr
+{
+    let body = s.trim();
+    let body = body
+        .strip_prefix('{')
+        .and_then(|b| b.strip_suffix('}'))
+        .unwrap_or(body);
+
+    body.split(',')
+        .map(str::trim)
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let column = offset(s, pair) + 1;
+            let (key, value) = pair
+                .split_once('=')
+                .ok_or_else(|| Error::parse("`<key>=<value>`", pair).at_column(column))?;
+            let value = value.trim();
+            let value = parse(value).map_err(|e| e.at_column(offset(s, value) + 1))?;
+
+            Ok((key.trim(), value))
+        })
+        .collect()
+}
+
+/// A type that can be read from a single line of input, usually declared with [`line_format!`].
+pub trait FromLine: Sized {
+    fn from_line(line: &str) -> Result<Self>;
+}
+
+/// How a field's `FromStr` error turns into an [`Error`] in [`capture`].
+pub trait IntoParseError {
+    fn into_parse_error(self, expected: &str, found: &str) -> Error;
+}
+
+/// Fields that already fail with an [`Error`] keep it, so its details survive.
+impl IntoParseError for Error {
+    fn into_parse_error(self, _expected: &str, _found: &str) -> Error {
+        self
+    }
+}
+
+macro_rules! std_parse_errors {
+    ($($ty:ty),*) => {
+        $(impl IntoParseError for $ty {
+            fn into_parse_error(self, expected: &str, found: &str) -> Error {
+                Error::parse(expected, found)
+            }
+        })*
+    };
+}
+
+std_parse_errors!(ParseIntError, ParseFloatError, std::char::ParseCharError, std::str::ParseBoolError);
+
+impl IntoParseError for Infallible {
+    fn into_parse_error(self, _expected: &str, _found: &str) -> Error {
+        match self {}
+    }
+}
+
+/// Parses the named capture `name`, pointing any error at the column the capture started on.
+/// Used by [`line_format!`].
+pub fn capture<T>(captures: &regex::Captures, name: &str) -> Result<T>
+where
+    T: FromStr,
+    T::Err: IntoParseError,
+{
+    let m = captures
+        .name(name)
+        .ok_or_else(|| Error::missing(format!("a `{name}` capture")))?;
+
+    m.as_str()
+        .parse()
+        .map_err(|e: T::Err| e.into_parse_error(std::any::type_name::<T>(), m.as_str()))
+        .map_err(|e| e.at_column(m.start() + 1))
+}
+
+/// Implements [`FromLine`] for a struct by matching a regex against the whole line and parsing
+/// each named capture into the field of the same name with `FromStr`. The regex is compiled the
+/// first time a line is parsed. The string in the middle is what errors say was expected.
+///
+/// ```ignore
+/// use crate::line_format;
+///
+/// struct Node { name: String, left: String, right: String }
+///
+/// line_format! {
+///     Node { name, left, right },
+///     "`<name> = (<left>, <right>)`",
+///     r"(?<name>\w+) = \((?<left>\w+), (?<right>\w+)\)",
+/// }
+///
+/// let nodes = map_lines(&text, Node::from_line)?;
+/// ```
+#[macro_export]
+macro_rules! line_format {
+    ($ty:ident { $($field:ident),* $(,)? }, $expected:literal, $re:literal $(,)?) => {
+        impl $crate::parse::FromLine for $ty {
+            fn from_line(line: &str) -> $crate::error::Result<Self> {
+                static RE: ::std::sync::OnceLock<::regex::Regex> = ::std::sync::OnceLock::new();
+                let re = RE.get_or_init(|| ::regex::Regex::new(concat!("^(?:", $re, ")$")).unwrap());
+
+                let captures = re
+                    .captures(line)
+                    .ok_or_else(|| $crate::error::Error::parse($expected, line))?;
+
+                Ok($ty {
+                    $($field: $crate::parse::capture(&captures, stringify!($field))?,)*
+                })
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn column(e: Error) -> Option<usize> {
+        match e {
+            Error::Parse { column, .. } => column,
+            other => panic!("expected a parse error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn ints_accept_commas_and_whitespace() {
+        assert_eq!(ints::<i32>("1,2, 3  -4").unwrap(), vec![1, 2, 3, -4]);
+        assert_eq!(column(ints::<u32>("1 2 x").unwrap_err()), Some(5));
+    }
+
+    #[test]
+    fn labeled_points_past_the_label() {
+        assert_eq!(labeled::<u64>("Time: 7 15", "Time").unwrap(), vec![7, 15]);
+        assert_eq!(column(labeled::<u64>("Time: 7 y", "Time").unwrap_err()), Some(9));
+        assert!(labeled::<u64>("Distance: 9", "Time").is_err());
+    }
+
+    #[test]
+    fn all_ints_skips_separators() {
+        assert_eq!(all_ints::<i32>("1,0,1~1,2,-1").unwrap(), vec![1, 0, 1, 1, 2, -1]);
+        assert_eq!(all_ints::<i32>("x=-3, y=4-5").unwrap(), vec![-3, 4, -5]);
+        assert_eq!(all_ints::<i32>("no numbers").unwrap(), Vec::<i32>::new());
+    }
+
+    #[test]
+    fn key_values_with_and_without_braces() {
+        assert_eq!(key_values::<u32>("{x=787,m=2655}").unwrap(), vec![("x", 787), ("m", 2655)]);
+        assert_eq!(key_values::<u32>("a=1, b = 2").unwrap(), vec![("a", 1), ("b", 2)]);
+        assert_eq!(key_values::<u32>("{}").unwrap(), vec![]);
+    }
+
+    #[test]
+    fn key_values_points_at_malformed_pairs() {
+        // missing `=`
+        assert_eq!(column(key_values::<u32>("{x=1,m2,a=3}").unwrap_err()), Some(6));
+        // bad value
+        assert_eq!(column(key_values::<u32>("x=1, m=two").unwrap_err()), Some(8));
+    }
+
+    #[derive(Debug)]
+    struct Move {
+        dir: crate::twod::Direction,
+        n: u32,
+    }
+
+    line_format! {
+        Move { dir, n },
+        "`<dir> <n>`",
+        r"(?<dir>\S+) (?<n>\S+)",
+    }
+
+    #[test]
+    fn line_format_points_at_the_bad_capture() {
+        let m = Move::from_line("R 12").unwrap();
+        assert_eq!((m.dir, m.n), (crate::twod::Direction::East, 12));
+
+        assert_eq!(column(Move::from_line("R x").unwrap_err()), Some(3));
+        assert!(matches!(
+            Move::from_line("Q 1").unwrap_err(),
+            Error::Parse { found, column: Some(1), .. } if found == "Q"
+        ));
+        assert!(Move::from_line("R").is_err());
+    }
+}
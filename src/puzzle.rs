@@ -1,65 +1,326 @@
 #![allow(dead_code)]
 
-use clap::Parser;
+use std::fmt::Display;
+use std::path::PathBuf;
 
-use std::fs;
+use crate::error::Result;
 
-#[derive(Parser, Debug)]
-struct Args {
-    #[arg(short, long)]
-    debug: bool,
+/// Which puzzle this is. Inputs, examples and answers are all filed under the year and then the
+/// day, so puzzles from different years never collide.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PuzzleId {
+    pub year: u32,
+    pub day: u32,
+}
+
+impl PuzzleId {
+    pub const fn new(year: u32, day: u32) -> Self {
+        PuzzleId { year, day }
+    }
+
+    /// `<year>/<day>`, relative to whichever directory the file lives in.
+    pub fn path(&self) -> PathBuf {
+        PathBuf::from(self.year.to_string()).join(self.day.to_string())
+    }
+}
+
+impl Display for PuzzleId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} day {}", self.year, self.day)
+    }
 }
 
 /// T is the type that the input gets parsed into
 /// R is the type that the answer comes in
-pub struct Puzzle<T, R> {
-    pub name: &'static str,
+/// D is how the input gets split before preprocessing, see [`Delimiter`]
+pub struct Puzzle<T, R, D: Delimiter = char> {
+    pub id: PuzzleId,
+    pub title: &'static str,
     pub parts: Vec<fn(&T) -> R>,
-    pub delimiter: char,
-    pub preprocess: fn(Vec<String>) -> T,
+    pub delimiter: D,
+    pub preprocess: fn(D::Output) -> Result<T>,
 }
 
-impl<T, R> Puzzle<T, R> {
+impl<T, R, D: Delimiter> Puzzle<T, R, D> {
     pub fn new(
-        name: &'static str,
+        id: PuzzleId,
+        title: &'static str,
         parts: Vec<fn(&T) -> R>,
-        delimiter: char,
-        preprocess: fn(Vec<String>) -> T,
+        delimiter: D,
+        preprocess: fn(D::Output) -> Result<T>,
     ) -> Self {
         Puzzle {
-            name,
+            id,
+            title,
             parts,
             delimiter,
             preprocess,
         }
     }
+}
+
+/// How a puzzle's input is split up before it reaches the preprocessor. A `char` or `&str`
+/// splits on that separator and hands over a `Vec<String>`; [`Paragraphs`] hands over blocks of
+/// lines.
+pub trait Delimiter {
+    type Output;
+
+    /// Splits `input`, which has already had trailing whitespace trimmed.
+    fn split(&self, input: &str) -> Self::Output;
+}
+
+impl Delimiter for char {
+    type Output = Vec<String>;
+
+    fn split(&self, input: &str) -> Self::Output {
+        input.split(*self).map(|x| x.to_string()).collect()
+    }
+}
+
+impl Delimiter for &str {
+    type Output = Vec<String>;
+
+    fn split(&self, input: &str) -> Self::Output {
+        input.split(*self).map(|x| x.to_string()).collect()
+    }
+}
+
+/// Splits the input into lines, grouped into blocks separated by blank lines. Every blank line
+/// ends a block, so the blocks joined with one blank line between each give back the input, and
+/// [`crate::util::paragraph_line`] can recover line numbers.
+pub struct Paragraphs;
+
+impl Delimiter for Paragraphs {
+    type Output = Vec<Vec<String>>;
+
+    fn split(&self, input: &str) -> Self::Output {
+        // lines() rather than split('\n'), so empty input is one empty block, not two
+        let mut blocks = vec![vec![]];
+        for line in input.lines() {
+            if line.is_empty() {
+                blocks.push(vec![]);
+            } else {
+                // there's always at least one block
+                blocks.last_mut().unwrap().push(line.to_string());
+            }
+        }
+
+        blocks
+    }
+}
+
+/// The answer to one part of a puzzle, independent of the type the part returned.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Int(i64),
+    BigInt(i128),
+    Str(String),
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Int(n) => n.fmt(f),
+            Answer::BigInt(n) => n.fmt(f),
+            Answer::Str(s) => s.fmt(f),
+        }
+    }
+}
+
+macro_rules! answer_from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Self {
+                    match i64::try_from(n) {
+                        Ok(n) => Answer::Int(n),
+                        Err(_) => Answer::BigInt(n as i128),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+answer_from_int!(i8, i16, i32, i64, u8, u16, u32, u64, usize, isize);
+
+impl From<i128> for Answer {
+    fn from(n: i128) -> Self {
+        Answer::BigInt(n)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Str(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Str(s.to_string())
+    }
+}
+
+/// Anything a part can return: a plain answer, or a `Result` of one for parts that can fail.
+pub trait IntoAnswer {
+    fn into_answer(self) -> Result<Answer>;
+}
+
+impl<R: Into<Answer>> IntoAnswer for R {
+    fn into_answer(self) -> Result<Answer> {
+        Ok(self.into())
+    }
+}
+
+impl<R: Into<Answer>> IntoAnswer for Result<R> {
+    fn into_answer(self) -> Result<Answer> {
+        self.map(Into::into)
+    }
+}
 
-    pub fn solve(self)
-    where
-        R: std::fmt::Display,
-    {
-        let dir = "inputs";
+/// A puzzle with its input and answer types erased, so that every day can live in one registry.
+///
+/// Solving happens in two steps so callers can time (or repeat) preprocessing and each part
+/// separately.
+pub trait Solver {
+    fn id(&self) -> PuzzleId;
 
-        let args = Args::parse();
-        let debug = args.debug;
+    /// The puzzle's title on the site, e.g. "Trebuchet?!".
+    fn title(&self) -> &str;
 
-        let filename = if debug {
-            "test.txt".to_string()
-        } else {
-            format!("{}/{}", dir, self.name)
-        };
+    /// Splits and preprocesses `input`.
+    fn prepare<'a>(&'a self, input: &str) -> Result<Box<dyn Prepared + 'a>>;
+
+    /// Preprocesses `input`, then runs each part in order.
+    fn solve(&self, input: &str) -> Result<Vec<Answer>> {
+        let prepared = self.prepare(input)?;
+        (0..prepared.parts()).map(|i| prepared.run(i)).collect()
+    }
+}
 
-        let contents = fs::read_to_string(filename).expect("Something went wrong reading the file");
-        let lines = contents
-            .trim_end()
-            .split(self.delimiter)
-            .map(|x| x.to_string())
-            .collect();
+/// A puzzle whose input has already been preprocessed.
+pub trait Prepared {
+    fn parts(&self) -> usize;
+
+    /// Runs part `i` (0-based). Parts can be run any number of times, in any order.
+    fn run(&self, i: usize) -> Result<Answer>;
+}
+
+impl<T, R, D> Solver for Puzzle<T, R, D>
+where
+    R: IntoAnswer,
+    D: Delimiter,
+{
+    fn id(&self) -> PuzzleId {
+        self.id
+    }
+
+    fn title(&self) -> &str {
+        self.title
+    }
 
-        let data = (self.preprocess)(lines);
+    fn prepare<'a>(&'a self, input: &str) -> Result<Box<dyn Prepared + 'a>> {
+        let data = (self.preprocess)(self.delimiter.split(input.trim_end()))?;
 
-        for f in self.parts {
-            println!("=> {}", f(&data));
+        Ok(Box::new(PreparedPuzzle { puzzle: self, data }))
+    }
+}
+
+struct PreparedPuzzle<'a, T, R, D: Delimiter> {
+    puzzle: &'a Puzzle<T, R, D>,
+    data: T,
+}
+
+impl<T, R, D> Prepared for PreparedPuzzle<'_, T, R, D>
+where
+    R: IntoAnswer,
+    D: Delimiter,
+{
+    fn parts(&self) -> usize {
+        self.puzzle.parts.len()
+    }
+
+    fn run(&self, i: usize) -> Result<Answer> {
+        (self.puzzle.parts[i])(&self.data).into_answer()
+    }
+}
+
+/// Several puzzles over the same input, whose parts are reported one after another. This is how
+/// a day gives each part its own preprocessing or its own answer type. The first puzzle's id and
+/// title stand for the whole day.
+pub struct Parts(pub Vec<Box<dyn Solver>>);
+
+impl Parts {
+    fn first(&self) -> &dyn Solver {
+        self.0.first().expect("`Parts` needs at least one puzzle").as_ref()
+    }
+}
+
+impl Solver for Parts {
+    fn id(&self) -> PuzzleId {
+        self.first().id()
+    }
+
+    fn title(&self) -> &str {
+        self.first().title()
+    }
+
+    fn prepare<'a>(&'a self, input: &str) -> Result<Box<dyn Prepared + 'a>> {
+        let prepared = self.0.iter()
+            .map(|p| p.prepare(input))
+            .collect::<Result<_>>()?;
+
+        Ok(Box::new(PreparedParts(prepared)))
+    }
+}
+
+struct PreparedParts<'a>(Vec<Box<dyn Prepared + 'a>>);
+
+impl Prepared for PreparedParts<'_> {
+    fn parts(&self) -> usize {
+        self.0.iter().map(|p| p.parts()).sum()
+    }
+
+    fn run(&self, i: usize) -> Result<Answer> {
+        let mut rest = i;
+        for p in self.0.iter() {
+            if rest < p.parts() {
+                return p.run(rest);
+            }
+            rest -= p.parts();
+        }
+
+        panic!("part {i} out of range");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn blocks(input: &str) -> Vec<Vec<String>> {
+        Paragraphs.split(input)
+    }
+
+    #[test]
+    fn paragraphs_end_at_every_blank_line() {
+        assert_eq!(blocks("a\nb\n\nc"), [vec!["a", "b"], vec!["c"]]);
+        // two blank lines in a row leave an empty block between them
+        assert_eq!(blocks("a\n\n\nb"), [vec!["a"], vec![], vec!["b"]]);
+        assert_eq!(blocks("\na"), [vec![], vec!["a"]]);
+        assert_eq!(blocks(""), [Vec::<&str>::new()]);
+
+        // putting one blank line between blocks gives back the input
+        for input in ["a\nb\n\nc", "a\n\n\nb", "\na", ""] {
+            assert_eq!(blocks(input).join(&String::new()).join("\n"), input);
         }
     }
+
+    #[test]
+    fn string_delimiters_split_on_the_whole_string() {
+        assert_eq!(Delimiter::split(&", ", "1, 2,3, 4"), ["1", "2,3", "4"]);
+        assert_eq!(Delimiter::split(&"->", "a->b"), ["a", "b"]);
+        assert_eq!(Delimiter::split(&',', "1,2,,3"), ["1", "2", "", "3"]);
+    }
 }
@@ -0,0 +1,215 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// The cheapest way to a goal state.
+#[derive(Debug, Clone)]
+pub struct Found<S, C> {
+    pub cost: C,
+    pub goal: S,
+
+    // How each visited state was first reached on its cheapest known route.
+    parents: HashMap<S, S>,
+}
+
+impl<S, C> Found<S, C>
+where
+    S: Clone + Hash + Eq,
+{
+    /// Every state from the start to the goal, inclusive.
+    pub fn path(&self) -> Vec<S> {
+        let mut path = vec![self.goal.clone()];
+        while let Some(parent) = self.parents.get(path.last().unwrap()) {
+            path.push(parent.clone());
+        }
+        path.reverse();
+        path
+    }
+}
+
+/// Breadth-first search where every step costs 1. The cost is the number of steps taken.
+pub fn bfs<S, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut goal: impl FnMut(&S) -> bool,
+) -> Option<Found<S, usize>>
+where
+    S: Clone + Hash + Eq,
+    I: IntoIterator<Item = S>,
+{
+    let mut parents = HashMap::new();
+    let mut steps = HashMap::from([(start.clone(), 0)]);
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(state) = queue.pop_front() {
+        let cost = steps[&state];
+        if goal(&state) {
+            return Some(Found { cost, goal: state, parents });
+        }
+
+        for next in successors(&state) {
+            if !steps.contains_key(&next) {
+                steps.insert(next.clone(), cost + 1);
+                parents.insert(next.clone(), state.clone());
+                queue.push_back(next);
+            }
+        }
+    }
+
+    None
+}
+
+/// Dijkstra's algorithm. `successors` yields each neighboring state with the (non-negative) cost
+/// of moving there.
+pub fn dijkstra<S, C, I>(
+    start: S,
+    successors: impl FnMut(&S) -> I,
+    goal: impl FnMut(&S) -> bool,
+) -> Option<Found<S, C>>
+where
+    S: Clone + Hash + Eq,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar(start, successors, goal, |_| C::default())
+}
+
+/// A* search. `heuristic` must never overestimate the remaining cost, or the result may not be
+/// the cheapest.
+pub fn astar<S, C, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut goal: impl FnMut(&S) -> bool,
+    mut heuristic: impl FnMut(&S) -> C,
+) -> Option<Found<S, C>>
+where
+    S: Clone + Hash + Eq,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut parents = HashMap::new();
+    let mut best = HashMap::from([(start.clone(), C::default())]);
+    let mut queue = BinaryHeap::from([Entry {
+        priority: heuristic(&start),
+        cost: C::default(),
+        state: start,
+    }]);
+
+    while let Some(Entry { cost, state, .. }) = queue.pop() {
+        // A cheaper route here was queued after this one and has already been expanded.
+        if best.get(&state).is_some_and(|&b| b < cost) {
+            continue;
+        }
+
+        if goal(&state) {
+            return Some(Found { cost, goal: state, parents });
+        }
+
+        for (next, step) in successors(&state) {
+            let next_cost = cost + step;
+            if best.get(&next).is_some_and(|&b| b <= next_cost) {
+                continue;
+            }
+
+            best.insert(next.clone(), next_cost);
+            parents.insert(next.clone(), state.clone());
+            queue.push(Entry {
+                priority: next_cost + heuristic(&next),
+                cost: next_cost,
+                state: next,
+            });
+        }
+    }
+
+    None
+}
+
+/// A queued state, ordered so that `BinaryHeap` pops the lowest priority first. States
+/// themselves needn't be comparable.
+struct Entry<S, C> {
+    priority: C,
+    cost: C,
+    state: S,
+}
+
+impl<S, C: Ord> Ord for Entry<S, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+impl<S, C: Ord> PartialOrd for Entry<S, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S, C: Ord> PartialEq for Entry<S, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<S, C: Ord> Eq for Entry<S, C> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::twod::{Coord, Map};
+
+    fn grid(rows: &[&str]) -> Map<char> {
+        Map::new(rows.iter().map(|r| r.chars().collect()).collect()).unwrap()
+    }
+
+    /// Entering a cell costs its digit.
+    fn weighted(map: &Map<char>) -> impl FnMut(&Coord) -> Vec<(Coord, u32)> + '_ {
+        |c| c.neighbors4().filter_map(|n| Some((n, map.get(n)?.to_digit(10)?))).collect()
+    }
+
+    fn open(map: &Map<char>) -> impl FnMut(&Coord) -> Vec<Coord> + '_ {
+        |c| c.neighbors4().filter(|&n| map.get(n).is_some_and(|t| t != '#')).collect()
+    }
+
+    #[test]
+    fn dijkstra_and_astar_take_the_cheap_route() {
+        let map = grid(&["119", "919", "911"]);
+        let goal = Coord::new(2, 2);
+        let cheapest = [(0, 0), (1, 0), (1, 1), (1, 2), (2, 2)].map(Coord::from);
+
+        let found = dijkstra(Coord::new(0, 0), weighted(&map), |&c| c == goal).unwrap();
+        assert_eq!(found.cost, 4);
+        assert_eq!(found.goal, goal);
+        assert_eq!(found.path(), cheapest);
+
+        let heuristic = |c: &Coord| c.manhattan(goal);
+        let found = astar(Coord::new(0, 0), weighted(&map), |&c| c == goal, heuristic).unwrap();
+        assert_eq!(found.cost, 4);
+        assert_eq!(found.path(), cheapest);
+    }
+
+    #[test]
+    fn bfs_counts_steps() {
+        let map = grid(&["..#.", ".##.", "...."]);
+        let goal = Coord::new(3, 0);
+
+        let found = bfs(Coord::new(0, 0), open(&map), |&c| c == goal).unwrap();
+        assert_eq!(found.cost, 7);
+        assert_eq!(found.path().len(), 8);
+        assert_eq!(found.path().first(), Some(&Coord::new(0, 0)));
+        assert!(found.path().windows(2).all(|w| w[0].manhattan(w[1]) == 1));
+
+        let found = bfs(goal, open(&map), |&c| c == goal).unwrap();
+        assert_eq!(found.cost, 0);
+        assert_eq!(found.path(), [goal]);
+    }
+
+    #[test]
+    fn unreachable_goals_are_none() {
+        let map = grid(&["1#1", "1#1"]);
+        let goal = Coord::new(2, 0);
+
+        assert!(bfs(Coord::new(0, 0), open(&map), |&c| c == goal).is_none());
+        assert!(dijkstra(Coord::new(0, 0), weighted(&map), |&c| c == goal).is_none());
+    }
+}
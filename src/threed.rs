@@ -0,0 +1,487 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::ops::{Add, Mul, Neg, Sub};
+
+use crate::error::Result;
+use crate::twod::{self, Map};
+use crate::util::parse;
+
+/// A point in space. z points up, so "above" means a larger z.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Coord {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+impl std::fmt::Display for Coord {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({},{},{})", self.x, self.y, self.z)
+    }
+}
+
+impl Coord {
+    pub fn new(x: i32, y: i32, z: i32) -> Self {
+        Self { x, y, z }
+    }
+
+    pub fn from_str(x: &str, y: &str, z: &str) -> Result<Self> {
+        Ok(Self {
+            x: parse(x)?,
+            y: parse(y)?,
+            z: parse(z)?,
+        })
+    }
+
+    pub fn above(&self) -> Self {
+        Self { z: self.z + 1, ..*self }
+    }
+
+    pub fn below(&self) -> Self {
+        Self { z: self.z - 1, ..*self }
+    }
+
+    /// The component along `axis`.
+    pub fn get(&self, axis: Axis) -> i32 {
+        match axis {
+            Axis::X => self.x,
+            Axis::Y => self.y,
+            Axis::Z => self.z,
+        }
+    }
+
+    pub fn manhattan(&self, other: Coord) -> u32 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y) + self.z.abs_diff(other.z)
+    }
+
+    /// The six neighbors that share a face.
+    pub fn neighbors6(&self) -> impl Iterator<Item = Coord> {
+        let c = *self;
+        [
+            Coord::new(c.x + 1, c.y, c.z),
+            Coord::new(c.x - 1, c.y, c.z),
+            Coord::new(c.x, c.y + 1, c.z),
+            Coord::new(c.x, c.y - 1, c.z),
+            c.above(),
+            c.below(),
+        ]
+        .into_iter()
+    }
+}
+
+impl Add for Coord {
+    type Output = Coord;
+
+    fn add(self, rhs: Coord) -> Coord {
+        Coord::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl Sub for Coord {
+    type Output = Coord;
+
+    fn sub(self, rhs: Coord) -> Coord {
+        Coord::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl Mul<i32> for Coord {
+    type Output = Coord;
+
+    fn mul(self, rhs: i32) -> Coord {
+        Coord::new(self.x * rhs, self.y * rhs, self.z * rhs)
+    }
+}
+
+impl Neg for Coord {
+    type Output = Coord;
+
+    fn neg(self) -> Coord {
+        Coord::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl From<(i32, i32, i32)> for Coord {
+    fn from((x, y, z): (i32, i32, i32)) -> Self {
+        Coord { x, y, z }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Axis {
+    X,
+    Y,
+    Z,
+}
+
+/// An axis-aligned box. Both corners are inside it.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Cuboid {
+    pub min: Coord,
+    pub max: Coord,
+}
+
+impl Cuboid {
+    /// The box with `a` and `b` as opposite corners, in either order.
+    pub fn new(a: Coord, b: Coord) -> Self {
+        Cuboid {
+            min: Coord::new(a.x.min(b.x), a.y.min(b.y), a.z.min(b.z)),
+            max: Coord::new(a.x.max(b.x), a.y.max(b.y), a.z.max(b.z)),
+        }
+    }
+
+    pub fn contains(&self, c: Coord) -> bool {
+        (self.min.x..=self.max.x).contains(&c.x)
+            && (self.min.y..=self.max.y).contains(&c.y)
+            && (self.min.z..=self.max.z).contains(&c.z)
+    }
+
+    /// Whether `other` fits entirely inside this box.
+    pub fn contains_cuboid(&self, other: &Cuboid) -> bool {
+        self.contains(other.min) && self.contains(other.max)
+    }
+
+    pub fn intersects(&self, other: &Cuboid) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    pub fn intersection(&self, other: &Cuboid) -> Option<Cuboid> {
+        let min = Coord::new(
+            self.min.x.max(other.min.x),
+            self.min.y.max(other.min.y),
+            self.min.z.max(other.min.z),
+        );
+        let max = Coord::new(
+            self.max.x.min(other.max.x),
+            self.max.y.min(other.max.y),
+            self.max.z.min(other.max.z),
+        );
+
+        (min.x <= max.x && min.y <= max.y && min.z <= max.z).then_some(Cuboid { min, max })
+    }
+
+    /// The smallest box containing both.
+    pub fn union(&self, other: &Cuboid) -> Cuboid {
+        let min = Coord::new(
+            self.min.x.min(other.min.x),
+            self.min.y.min(other.min.y),
+            self.min.z.min(other.min.z),
+        );
+        let max = Coord::new(
+            self.max.x.max(other.max.x),
+            self.max.y.max(other.max.y),
+            self.max.z.max(other.max.z),
+        );
+
+        Cuboid { min, max }
+    }
+
+    pub fn translate(&self, delta: Coord) -> Cuboid {
+        Cuboid { min: self.min + delta, max: self.max + delta }
+    }
+
+    pub fn volume(&self) -> u64 {
+        let side = |axis| (self.max.get(axis) - self.min.get(axis)) as u64 + 1;
+        side(Axis::X) * side(Axis::Y) * side(Axis::Z)
+    }
+
+    /// Every point in the box, x fastest and z slowest.
+    pub fn points(&self) -> impl Iterator<Item = Coord> {
+        let Cuboid { min, max } = *self;
+        (min.z..=max.z).flat_map(move |z| {
+            (min.y..=max.y).flat_map(move |y| (min.x..=max.x).map(move |x| Coord::new(x, y, z)))
+        })
+    }
+
+    /// The (x, y) cells the box covers when seen from above.
+    pub fn footprint(&self) -> impl Iterator<Item = twod::Coord> {
+        let Cuboid { min, max } = *self;
+        (min.y..=max.y).flat_map(move |y| (min.x..=max.x).map(move |x| twod::Coord::new(x, y)))
+    }
+}
+
+/// A sparse grid of voxels. Only filled voxels are stored, so it suits spaces that are mostly
+/// empty or have no natural bounds.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Voxels<T> {
+    data: HashMap<Coord, T>,
+
+    // Only `insert` and `fill` touch this, so after `remove` or `clear` projections and renders
+    // keep their old size, padded with empty cells.
+    bounds: Option<Cuboid>,
+}
+
+impl<T> Voxels<T> {
+    pub fn new() -> Self {
+        Voxels { data: HashMap::new(), bounds: None }
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// The extent that [`Voxels::projection`] and [`Voxels::render`] draw, or None before the first
+    /// insert. Emptying voxels doesn't shrink it.
+    pub fn bounds(&self) -> Option<Cuboid> {
+        self.bounds
+    }
+
+    pub fn get(&self, c: Coord) -> Option<&T> {
+        self.data.get(&c)
+    }
+
+    pub fn contains(&self, c: Coord) -> bool {
+        self.data.contains_key(&c)
+    }
+
+    pub fn insert(&mut self, c: Coord, v: T) -> Option<T> {
+        let point = Cuboid::new(c, c);
+        self.bounds = Some(self.bounds.map_or(point, |b| b.union(&point)));
+        self.data.insert(c, v)
+    }
+
+    pub fn remove(&mut self, c: Coord) -> Option<T> {
+        self.data.remove(&c)
+    }
+
+    /// Empties every voxel in `cuboid`.
+    pub fn clear(&mut self, cuboid: &Cuboid) {
+        for c in cuboid.points() {
+            self.data.remove(&c);
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Coord, &T)> {
+        self.data.iter().map(|(&c, v)| (c, v))
+    }
+}
+
+impl<T: Clone> Voxels<T> {
+    /// Fills every voxel in `cuboid` with `v`.
+    pub fn fill(&mut self, cuboid: &Cuboid, v: T) {
+        for c in cuboid.points() {
+            self.insert(c, v.clone());
+        }
+    }
+}
+
+impl<T: Copy> Voxels<T> {
+    /// Lays the voxels out on a plane perpendicular to `axis`, as seen from the far (high) end
+    /// of it, so the voxel with the largest coordinate along `axis` wins. Looking along x or y
+    /// gives a side view with z going up the page; looking along z gives a view from above with
+    /// y going down the page. The map starts at the bounds' corner, so map (0, 0) is rarely the
+    /// origin.
+    pub fn projection(&self, axis: Axis) -> Map<Option<T>> {
+        self.flatten(axis, |_, existing| existing.is_none())
+    }
+
+    /// The voxels whose coordinate along `axis` is `at`, laid out as in [`Voxels::projection`].
+    pub fn slice(&self, axis: Axis, at: i32) -> Map<Option<T>> {
+        self.flatten(axis, |c, _| c.get(axis) == at)
+    }
+
+    /// Builds a plane perpendicular to `axis`, taking voxels (highest along `axis` first) for
+    /// which `keep(voxel, current cell)` says so.
+    fn flatten(&self, axis: Axis, keep: impl Fn(Coord, Option<T>) -> bool) -> Map<Option<T>> {
+        let Some(Cuboid { min, max }) = self.bounds else {
+            return Map::default();
+        };
+
+        // (column, row) on the page for a point
+        let place = |c: Coord| match axis {
+            Axis::X => twod::Coord::new(c.y - min.y, max.z - c.z),
+            Axis::Y => twod::Coord::new(c.x - min.x, max.z - c.z),
+            Axis::Z => twod::Coord::new(c.x - min.x, c.y - min.y),
+        };
+        let size = place(Coord::new(max.x, max.y, min.z));
+
+        let mut map = Map::empty(size.x as usize + 1, size.y as usize + 1, None);
+        let mut voxels: Vec<_> = self.iter().collect();
+        voxels.sort_by_key(|(c, _)| std::cmp::Reverse(c.get(axis)));
+
+        for (c, &v) in voxels {
+            let cell = place(c);
+            if keep(c, map.get(cell).flatten()) {
+                map.set(cell, Some(v));
+            }
+        }
+
+        map
+    }
+}
+
+impl<T: Copy + Display> Voxels<T> {
+    /// Draws [`Voxels::projection`] with `.` for empty cells. Side views label each row with its z.
+    pub fn render(&self, axis: Axis) -> String {
+        let Some(bounds) = self.bounds else {
+            return String::new();
+        };
+
+        let map = self.projection(axis);
+        map.rows()
+            .enumerate()
+            .map(|(i, row)| {
+                let row = row.iter()
+                    .map(|v| v.map_or(".".to_string(), |v| v.to_string()))
+                    .collect::<String>();
+                match axis {
+                    Axis::X | Axis::Y => format!("{}: {row}", bounds.max.z - i as i32),
+                    Axis::Z => row,
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Drops each box straight down (towards smaller z) until it rests on the floor or on another
+/// box, lowest first. `floor` is the lowest z a box can occupy. Returns the settled boxes in the
+/// same order they were given.
+pub fn settle(cuboids: &[Cuboid], floor: i32) -> Vec<Cuboid> {
+    let mut order: Vec<usize> = (0..cuboids.len()).collect();
+    order.sort_by_key(|&i| cuboids[i].min.z);
+
+    // highest occupied z over each (x, y) so far
+    let mut heights: HashMap<twod::Coord, i32> = HashMap::new();
+    let mut settled = cuboids.to_vec();
+
+    for i in order {
+        let cuboid = cuboids[i];
+        let rest = cuboid.footprint()
+            .filter_map(|c| heights.get(&c).map(|h| h + 1))
+            .fold(floor, std::cmp::max);
+
+        let dropped = cuboid.translate(Coord::new(0, 0, rest - cuboid.min.z));
+        for c in dropped.footprint() {
+            heights.insert(c, dropped.max.z);
+        }
+        settled[i] = dropped;
+    }
+
+    settled
+}
+
+/// For boxes that have already settled, which boxes each one rests directly on (`below`) and
+/// which rest directly on it (`above`), by index.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Supports {
+    pub below: Vec<Vec<usize>>,
+    pub above: Vec<Vec<usize>>,
+}
+
+impl Supports {
+    pub fn new(cuboids: &[Cuboid]) -> Self {
+        let mut owners = Voxels::new();
+        for (i, cuboid) in cuboids.iter().enumerate() {
+            owners.fill(cuboid, i);
+        }
+
+        let mut supports = Supports {
+            below: vec![vec![]; cuboids.len()],
+            above: vec![vec![]; cuboids.len()],
+        };
+
+        for (i, cuboid) in cuboids.iter().enumerate() {
+            let mut below: Vec<usize> = cuboid.footprint()
+                .filter_map(|c| owners.get(Coord::new(c.x, c.y, cuboid.min.z - 1)).copied())
+                .collect();
+            below.sort();
+            below.dedup();
+
+            for &j in below.iter() {
+                supports.above[j].push(i);
+            }
+            supports.below[i] = below;
+        }
+
+        supports
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cuboid(a: (i32, i32, i32), b: (i32, i32, i32)) -> Cuboid {
+        Cuboid::new(a.into(), b.into())
+    }
+
+    #[test]
+    fn cuboid_containment() {
+        let outer = cuboid((0, 0, 0), (3, 3, 3));
+        assert!(outer.contains_cuboid(&cuboid((1, 1, 1), (2, 2, 2))));
+        assert!(outer.contains_cuboid(&outer));
+        assert!(outer.contains_cuboid(&cuboid((3, 0, 3), (3, 0, 3))));
+        assert!(!outer.contains_cuboid(&cuboid((2, 2, 2), (4, 3, 3))));
+        assert!(!cuboid((1, 1, 1), (2, 2, 2)).contains_cuboid(&outer));
+
+        let overlap = outer.intersection(&cuboid((2, 2, 2), (4, 3, 3)));
+        assert_eq!(overlap, Some(cuboid((2, 2, 2), (3, 3, 3))));
+        assert_eq!(overlap.unwrap().volume(), 8);
+        assert!(!outer.intersects(&cuboid((4, 0, 0), (5, 0, 0))));
+    }
+
+    #[test]
+    fn settle_stacks_bricks() {
+        // given top brick first, to check the order is kept
+        let bricks = [
+            cuboid((1, 0, 9), (1, 2, 9)),
+            cuboid((0, 0, 5), (2, 0, 5)),
+            cuboid((5, 5, 7), (5, 5, 8)),
+        ];
+
+        let settled = settle(&bricks, 1);
+        assert_eq!(settled, [
+            cuboid((1, 0, 2), (1, 2, 2)),
+            cuboid((0, 0, 1), (2, 0, 1)),
+            cuboid((5, 5, 1), (5, 5, 2)),
+        ]);
+
+        let supports = Supports::new(&settled);
+        assert_eq!(supports.below, [vec![1], vec![], vec![]]);
+        assert_eq!(supports.above, [vec![], vec![0], vec![]]);
+    }
+
+    fn sample() -> Voxels<char> {
+        let mut voxels = Voxels::new();
+        voxels.insert(Coord::new(0, 0, 0), 'a');
+        voxels.insert(Coord::new(1, 0, 0), 'b');
+        voxels.insert(Coord::new(0, 1, 1), 'c');
+        voxels
+    }
+
+    #[test]
+    fn bounds_never_shrink() {
+        let mut voxels = sample();
+        assert_eq!(voxels.bounds(), Some(cuboid((0, 0, 0), (1, 1, 1))));
+        voxels.remove(Coord::new(0, 1, 1));
+        assert_eq!(voxels.bounds(), Some(cuboid((0, 0, 0), (1, 1, 1))));
+        assert_eq!(Voxels::<char>::new().bounds(), None);
+    }
+
+    #[test]
+    fn renders_along_each_axis() {
+        let voxels = sample();
+        // from above, y down the page
+        assert_eq!(voxels.render(Axis::Z), "ab\nc.");
+        // side views, z up the page; the voxel furthest along the axis wins
+        assert_eq!(voxels.render(Axis::X), "1: .c\n0: b.");
+        assert_eq!(voxels.render(Axis::Y), "1: c.\n0: ab");
+        assert_eq!(Voxels::<char>::new().render(Axis::Z), "");
+    }
+
+    #[test]
+    fn slices_one_layer() {
+        let voxels = sample();
+        let rows = |map: Map<Option<char>>| map.rows().map(|r| r.to_vec()).collect::<Vec<_>>();
+
+        assert_eq!(rows(voxels.slice(Axis::Z, 0)), [[Some('a'), Some('b')], [None, None]]);
+        assert_eq!(rows(voxels.slice(Axis::X, 1)), [[None, None], [Some('b'), None]]);
+        assert_eq!(rows(voxels.slice(Axis::Y, 1)), [[Some('c'), None], [None, None]]);
+        assert_eq!(rows(voxels.slice(Axis::Z, 5)), [[None, None], [None, None]]);
+    }
+}
@@ -1,4 +1,10 @@
+use std::collections::{HashMap, VecDeque};
 use std::fmt::Display;
+use std::num::TryFromIntError;
+use std::ops::{Add, AddAssign, Index, IndexMut, Mul, Neg, Sub, SubAssign};
+
+use crate::error::{Error, Result};
+use crate::util::parse;
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub struct Coord {
@@ -17,11 +23,11 @@ impl Coord {
         Self { x, y }
     }
 
-    pub fn from_str(x: &str, y: &str) -> Self {
-        Self {
-            x: x.parse().unwrap(),
-            y: y.parse().unwrap(),
-        }
+    pub fn from_str(x: &str, y: &str) -> Result<Self> {
+        Ok(Self {
+            x: parse(x)?,
+            y: parse(y)?,
+        })
     }
 
     pub fn from_usize(x: usize, y: usize) -> Self {
@@ -32,12 +38,7 @@ impl Coord {
     }
 
     pub fn step(&self, dir: Direction) -> Coord {
-        match dir {
-            Direction::North => self.north(),
-            Direction::East => self.east(),
-            Direction::South => self.south(),
-            Direction::West => self.west(),
-        }
+        *self + dir.delta()
     }
 
     pub fn north(&self) -> Coord {
@@ -55,96 +56,681 @@ impl Coord {
     pub fn east(&self) -> Coord {
         Coord { x: self.x+1, y: self.y }
     }
+
+    /// Taxicab distance: the number of orthogonal steps between the two.
+    pub fn manhattan(&self, other: Coord) -> u32 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    /// Chessboard distance: the number of steps between the two when diagonal steps are allowed.
+    pub fn chebyshev(&self, other: Coord) -> u32 {
+        std::cmp::max(self.x.abs_diff(other.x), self.y.abs_diff(other.y))
+    }
+
+    /// The four orthogonal neighbors, clockwise from north.
+    pub fn neighbors4(&self) -> impl Iterator<Item = Coord> {
+        let c = *self;
+        [c.north(), c.east(), c.south(), c.west()].into_iter()
+    }
+
+    /// All eight neighbors, including diagonals, clockwise from north.
+    pub fn neighbors8(&self) -> impl Iterator<Item = Coord> {
+        let c = *self;
+        [
+            c.north(),
+            c.north().east(),
+            c.east(),
+            c.south().east(),
+            c.south(),
+            c.south().west(),
+            c.west(),
+            c.north().west(),
+        ]
+        .into_iter()
+    }
+
+    /// Rotates a quarter turn clockwise about the origin. With y pointing south, north becomes
+    /// east.
+    pub fn rotate_cw(&self) -> Coord {
+        Coord { x: -self.y, y: self.x }
+    }
+
+    /// Rotates a quarter turn counterclockwise about the origin, so north becomes west.
+    pub fn rotate_ccw(&self) -> Coord {
+        Coord { x: self.y, y: -self.x }
+    }
+
+    /// Rotates a quarter turn clockwise about `center` rather than the origin.
+    pub fn rotate_cw_about(&self, center: Coord) -> Coord {
+        (*self - center).rotate_cw() + center
+    }
+
+    /// Rotates a quarter turn counterclockwise about `center` rather than the origin.
+    pub fn rotate_ccw_about(&self, center: Coord) -> Coord {
+        (*self - center).rotate_ccw() + center
+    }
+}
+
+impl Add for Coord {
+    type Output = Coord;
+
+    fn add(self, rhs: Coord) -> Coord {
+        Coord { x: self.x + rhs.x, y: self.y + rhs.y }
+    }
+}
+
+impl AddAssign for Coord {
+    fn add_assign(&mut self, rhs: Coord) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Coord {
+    type Output = Coord;
+
+    fn sub(self, rhs: Coord) -> Coord {
+        Coord { x: self.x - rhs.x, y: self.y - rhs.y }
+    }
+}
+
+impl SubAssign for Coord {
+    fn sub_assign(&mut self, rhs: Coord) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul<i32> for Coord {
+    type Output = Coord;
+
+    fn mul(self, rhs: i32) -> Coord {
+        Coord { x: self.x * rhs, y: self.y * rhs }
+    }
+}
+
+impl Neg for Coord {
+    type Output = Coord;
+
+    fn neg(self) -> Coord {
+        Coord { x: -self.x, y: -self.y }
+    }
+}
+
+impl From<(i32, i32)> for Coord {
+    fn from((x, y): (i32, i32)) -> Self {
+        Coord { x, y }
+    }
+}
+
+impl From<Coord> for (i32, i32) {
+    fn from(c: Coord) -> Self {
+        (c.x, c.y)
+    }
+}
+
+impl From<(usize, usize)> for Coord {
+    fn from((x, y): (usize, usize)) -> Self {
+        Coord::from_usize(x, y)
+    }
+}
+
+/// Fails if either component is negative.
+impl TryFrom<Coord> for (usize, usize) {
+    type Error = TryFromIntError;
+
+    fn try_from(c: Coord) -> std::result::Result<Self, Self::Error> {
+        Ok((usize::try_from(c.x)?, usize::try_from(c.y)?))
+    }
 }
 
 #[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
 pub enum Direction {
     North,
+    NorthEast,
     East,
+    SouthEast,
     South,
+    SouthWest,
     West,
+    NorthWest,
+}
+
+/// Every direction, clockwise from north. Turning is a walk around this array.
+const COMPASS: [Direction; 8] = [
+    Direction::North,
+    Direction::NorthEast,
+    Direction::East,
+    Direction::SouthEast,
+    Direction::South,
+    Direction::SouthWest,
+    Direction::West,
+    Direction::NorthWest,
+];
+
+impl Direction {
+    /// The four orthogonal directions, clockwise from north.
+    pub fn all() -> [Direction; 4] {
+        [Direction::North, Direction::East, Direction::South, Direction::West]
+    }
+
+    /// All eight directions, including diagonals, clockwise from north.
+    pub fn all8() -> [Direction; 8] {
+        COMPASS
+    }
+
+    fn rotate(&self, eighths: usize) -> Direction {
+        COMPASS[(*self as usize + eighths) % COMPASS.len()]
+    }
+
+    /// A quarter turn counterclockwise, so north becomes west.
+    pub fn turn_left(&self) -> Direction {
+        self.rotate(6)
+    }
+
+    /// A quarter turn clockwise, so north becomes east.
+    pub fn turn_right(&self) -> Direction {
+        self.rotate(2)
+    }
+
+    pub fn opposite(&self) -> Direction {
+        self.rotate(4)
+    }
+
+    pub fn is_diagonal(&self) -> bool {
+        *self as usize % 2 == 1
+    }
+
+    /// North or south.
+    pub fn is_vertical(&self) -> bool {
+        matches!(self, Direction::North | Direction::South)
+    }
+
+    /// East or west.
+    pub fn is_horizontal(&self) -> bool {
+        matches!(self, Direction::East | Direction::West)
+    }
+
+    /// The offset of one step in this direction. y points south.
+    pub fn delta(&self) -> Coord {
+        match self {
+            Direction::North => Coord::new(0, -1),
+            Direction::NorthEast => Coord::new(1, -1),
+            Direction::East => Coord::new(1, 0),
+            Direction::SouthEast => Coord::new(1, 1),
+            Direction::South => Coord::new(0, 1),
+            Direction::SouthWest => Coord::new(-1, 1),
+            Direction::West => Coord::new(-1, 0),
+            Direction::NorthWest => Coord::new(-1, -1),
+        }
+    }
 }
 
+/// Accepts `URDL`, `NESW` and `^>v<`.
+impl TryFrom<char> for Direction {
+    type Error = Error;
+
+    fn try_from(c: char) -> Result<Self> {
+        match c {
+            'U' | 'N' | '^' => Ok(Direction::North),
+            'R' | 'E' | '>' => Ok(Direction::East),
+            'D' | 'S' | 'v' => Ok(Direction::South),
+            'L' | 'W' | '<' => Ok(Direction::West),
+            _ => Err(Error::parse("one of `URDL`, `NESW` or `^>v<`", c)),
+        }
+    }
+}
+
+/// Parses a single character, as [`Direction::try_from`] does.
+impl std::str::FromStr for Direction {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Direction::try_from(c),
+            _ => Err(Error::parse("one of `URDL`, `NESW` or `^>v<`", s)),
+        }
+    }
+}
+
+/// A rectangular grid, stored row by row in one `Vec`. (0, 0) is the top left corner and y
+/// grows down the page.
 #[derive(Debug, Clone, Hash, PartialEq, Eq, Default)]
 pub struct Map<T> {
-    pub data: Vec<Vec<T>>,
-
-    // These should be usize but it's annoying to deal with underflows.
-    pub xmax: i32,
-    pub ymax: i32,
+    data: Vec<T>,
+    width: usize,
+    height: usize,
 }
 
-pub fn char_map_from_strings(data: Vec<String>) -> Map<char> {
+/// One cell per character. Unlike [`Map::new`], fails if there are no cells at all: no puzzle
+/// has an empty grid, so this saves every day from checking for a start or corner that isn't
+/// there.
+pub fn char_map_from_strings(data: Vec<String>) -> Result<Map<char>> {
+    if data.iter().all(|line| line.is_empty()) {
+        return Err(Error::missing("a grid"));
+    }
+
     Map::new(data.into_iter().map(|line| line.chars().collect()).collect())
 }
 
-impl<T> Map<T>
-where
-    T: Copy
-{
-    pub fn new(data: Vec<Vec<T>>) -> Self {
-        let ymax = data.len() as i32;
-        let xmax = data[0].len() as i32;
+impl<T> Map<T> {
+    /// Builds a map from its rows. Every row must be as long as the first; the error for a
+    /// ragged row carries its (1-based) line.
+    pub fn new(rows: Vec<Vec<T>>) -> Result<Self> {
+        let height = rows.len();
+        let width = rows.first().map_or(0, |row| row.len());
+
+        let mut data = Vec::with_capacity(width * height);
+        for (y, row) in rows.into_iter().enumerate() {
+            if row.len() != width {
+                return Err(Error::parse(
+                    format!("a row of {width} cells"),
+                    format!("{} cells", row.len()),
+                ).at_line(y + 1));
+            }
+            data.extend(row);
+        }
+
+        Ok(Self { data, width, height })
+    }
 
-        Self { data, ymax, xmax }
+    pub fn empty(width: usize, height: usize, val: T) -> Self
+    where
+        T: Clone
+    {
+        Self {
+            data: vec![val; width * height],
+            width,
+            height,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
     }
 
-    pub fn empty(xmax: i32, ymax: i32, val: T) -> Self {
-        let data = (0..ymax)
-            .map(|_| (0..xmax).map(|_| val).collect::<Vec<_>>())
-            .collect::<Vec<_>>();
+    pub fn height(&self) -> usize {
+        self.height
+    }
 
-        Self::new(data)
+    /// One past the largest x, for doing arithmetic with [`Coord`]s.
+    pub fn xmax(&self) -> i32 {
+        self.width as i32
+    }
+
+    /// One past the largest y, for doing arithmetic with [`Coord`]s.
+    pub fn ymax(&self) -> i32 {
+        self.height as i32
+    }
+
+    /// True if the map has no cells, which is also the case for rows of width 0.
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
     }
 
     pub fn in_bounds(&self, c: Coord) -> bool {
-        c.x >= 0 && c.y >= 0 && c.x < self.xmax && c.y < self.ymax
+        c.x >= 0 && c.y >= 0 && c.x < self.xmax() && c.y < self.ymax()
     }
 
-    pub fn get(&self, c: Coord) -> Option<T> {
-        if !self.in_bounds(c) {
-            None
-        } else {
-            Some(self.data[c.y as usize][c.x as usize])
-        }
+    fn index_of(&self, c: Coord) -> Option<usize> {
+        self.in_bounds(c).then(|| c.y as usize * self.width + c.x as usize)
+    }
+
+    pub fn get_ref(&self, c: Coord) -> Option<&T> {
+        self.index_of(c).map(|i| &self.data[i])
+    }
+
+    pub fn get_mut(&mut self, c: Coord) -> Option<&mut T> {
+        self.index_of(c).map(|i| &mut self.data[i])
     }
 
     pub fn set(&mut self, c: Coord, v: T) {
-        if !self.in_bounds(c) {
-            panic!("set out of bounds");
+        self[c] = v;
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.data[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn row_mut(&mut self, y: usize) -> &mut [T] {
+        &mut self.data[y * self.width..(y + 1) * self.width]
+    }
+
+    /// Rows from top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    /// The cells in column `x`, from top to bottom.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {x} out of bounds for width {}", self.width);
+        self.data.iter().skip(x).step_by(self.width)
+    }
+
+    /// Columns from left to right.
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Every cell with its coordinate, in reading order.
+    pub fn iter(&self) -> impl Iterator<Item = (Coord, &T)> {
+        self.data.iter().enumerate().map(|(i, v)| (self.coord_of(i), v))
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Coord, &mut T)> {
+        let width = self.width;
+        self.data.iter_mut().enumerate().map(move |(i, v)| (Coord::from((i % width, i / width)), v))
+    }
+
+    fn coord_of(&self, i: usize) -> Coord {
+        Coord::from((i % self.width, i / self.width))
+    }
+
+    /// Wraps `c` around the edges, as if the map were tiled infinitely in every direction. An
+    /// empty map has nowhere to wrap to, so gives None.
+    pub fn wrap_coord(&self, c: Coord) -> Option<Coord> {
+        if self.is_empty() {
+            return None;
         }
 
-        self.data[c.y as usize][c.x as usize] = v;
+        Some(Coord {
+            x: c.x.rem_euclid(self.xmax()),
+            y: c.y.rem_euclid(self.ymax()),
+        })
     }
+}
 
-    pub fn get_with_wraparound(&self, c: Coord) -> Option<T> {
-        let wrapped = self.wrap_coord(c);
-        self.get(wrapped)
+impl<T: Clone> Map<T> {
+    /// Builds a `width` x `height` map by calling `f` on each coordinate in reading order.
+    fn build(width: usize, height: usize, f: impl Fn(usize, usize) -> T) -> Self {
+        let data = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| f(x, y))
+            .collect();
+
+        Self { data, width, height }
+    }
+
+    /// Swaps rows and columns, mirroring the map across its top-left to bottom-right diagonal.
+    pub fn transpose(&self) -> Self {
+        Self::build(self.height, self.width, |x, y| self[(y, x)].clone())
+    }
+
+    /// A quarter turn clockwise: the left column becomes the top row.
+    pub fn rotate_cw(&self) -> Self {
+        Self::build(self.height, self.width, |x, y| self[(y, self.height - 1 - x)].clone())
     }
 
-    pub fn wrap_coord(&self, c: Coord) -> Coord {
-        Coord {
-            x: wrap(c.x, self.xmax),
-            y: wrap(c.y, self.ymax),
+    /// A quarter turn counterclockwise: the top row becomes the left column.
+    pub fn rotate_ccw(&self) -> Self {
+        Self::build(self.height, self.width, |x, y| self[(self.width - 1 - y, x)].clone())
+    }
+
+    pub fn rotate_180(&self) -> Self {
+        let mut map = self.clone();
+        map.data.reverse();
+        map
+    }
+}
+
+impl<T> Map<T> {
+    /// Mirrors left to right, in place.
+    pub fn flip_horizontal(&mut self) {
+        for y in 0..self.height {
+            self.row_mut(y).reverse();
+        }
+    }
+
+    /// Mirrors top to bottom, in place.
+    pub fn flip_vertical(&mut self) {
+        for y in 0..self.height / 2 {
+            let (top, bottom) = self.data.split_at_mut((self.height - 1 - y) * self.width);
+            top[y * self.width..(y + 1) * self.width].swap_with_slice(&mut bottom[..self.width]);
+        }
+    }
+
+    /// Moves every `movable` cell as far as it goes in `dir`, in place. Cells only move through
+    /// `empty` ones, trading places with them, and stop at the edge or against anything else,
+    /// including movable cells that have already stopped.
+    pub fn slide(&mut self, dir: Direction, movable: impl Fn(&T) -> bool, empty: impl Fn(&T) -> bool) {
+        let delta = dir.delta();
+
+        // Each line of cells along dir ends at a cell whose next step leaves the map. Walk every
+        // line back from there, remembering the furthest free cell a movable one could reach.
+        let ends: Vec<_> = (0..self.data.len())
+            .map(|i| self.coord_of(i))
+            .filter(|&c| !self.in_bounds(c + delta))
+            .collect();
+
+        for end in ends {
+            let mut free = end;
+            let mut c = end;
+            while let Some(i) = self.index_of(c) {
+                if movable(&self.data[i]) {
+                    if free != c {
+                        let f = self.index_of(free).unwrap();
+                        self.data.swap(f, i);
+                    }
+                    free -= delta;
+                } else if !empty(&self.data[i]) {
+                    free = c - delta;
+                }
+                c -= delta;
+            }
         }
     }
 }
 
-fn wrap(a: i32, b: i32) -> i32 {
-    ((a % b) + b) % b
+impl<T: Copy> Map<T> {
+    pub fn get(&self, c: Coord) -> Option<T> {
+        self.get_ref(c).copied()
+    }
+
+    /// Like [`Map::get`] on the infinite tiling of the map. Only an empty map has nothing there.
+    pub fn get_with_wraparound(&self, c: Coord) -> Option<T> {
+        self.get(self.wrap_coord(c)?)
+    }
 }
 
 impl<T: PartialEq> Map<T> {
+    /// The first cell holding `target` in reading order, or (-1, -1) if there isn't one.
     pub fn find(&self, target: &T) -> Coord {
-        for (y, line) in self.data.iter().enumerate() {
-            for (x, c) in line.iter().enumerate() {
-                if c == target {
-                    return Coord { x: x as i32, y: y as i32 };
+        self.data
+            .iter()
+            .position(|v| v == target)
+            .map_or(Coord { x: -1, y: -1 }, |i| self.coord_of(i))
+    }
+}
+
+/// Which cells count as touching when filling regions.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Connectivity {
+    /// Only orthogonal neighbors.
+    Four,
+    /// Diagonal neighbors too.
+    Eight,
+}
+
+impl Connectivity {
+    pub fn neighbors(&self, c: Coord) -> impl Iterator<Item = Coord> {
+        // neighbors8 alternates orthogonal and diagonal, starting with north.
+        let step = match self {
+            Connectivity::Four => 2,
+            Connectivity::Eight => 1,
+        };
+        c.neighbors8().step_by(step)
+    }
+}
+
+/// The result of [`Map::regions`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Regions {
+    /// The region each cell belongs to. Regions are numbered from 0 in the reading order of
+    /// their first cell.
+    pub labels: Map<usize>,
+    /// The number of cells in each region.
+    pub sizes: Vec<usize>,
+}
+
+impl<T> Map<T> {
+    /// Every cell reachable from `start` through cells that are `passable`, including `start`
+    /// itself, in the order they were reached. Empty if `start` isn't passable or is off the map.
+    pub fn flood_fill(
+        &self,
+        start: Coord,
+        connectivity: Connectivity,
+        passable: impl Fn(&T) -> bool,
+    ) -> Vec<Coord> {
+        if !self.get_ref(start).is_some_and(&passable) {
+            return vec![];
+        }
+
+        let mut seen = Map::empty(self.width, self.height, false);
+        seen[start] = true;
+
+        let mut region = vec![start];
+        let mut i = 0;
+        while let Some(&c) = region.get(i) {
+            for next in connectivity.neighbors(c) {
+                if self.get_ref(next).is_some_and(&passable) && !seen[next] {
+                    seen[next] = true;
+                    region.push(next);
                 }
             }
+            i += 1;
         }
 
-        Coord { x: -1, y: -1 }
+        region
+    }
+
+    /// Splits the whole map into regions: neighboring cells share one if `connected` says so,
+    /// which is usually equality.
+    pub fn regions(&self, connectivity: Connectivity, connected: impl Fn(&T, &T) -> bool) -> Regions {
+        let mut labels: Map<Option<usize>> = Map::empty(self.width, self.height, None);
+        let mut sizes = vec![];
+
+        for i in 0..self.data.len() {
+            let start = self.coord_of(i);
+            if labels[start].is_some() {
+                continue;
+            }
+
+            let label = sizes.len();
+            labels[start] = Some(label);
+
+            let mut stack = vec![start];
+            let mut size = 0;
+            while let Some(c) = stack.pop() {
+                size += 1;
+                for next in connectivity.neighbors(c) {
+                    if labels.get(next) == Some(None) && connected(&self[c], &self[next]) {
+                        labels[next] = Some(label);
+                        stack.push(next);
+                    }
+                }
+            }
+            sizes.push(size);
+        }
+
+        let labels = Map {
+            data: labels.data.into_iter().map(|l| l.expect("every cell is labeled")).collect(),
+            width: self.width,
+            height: self.height,
+        };
+
+        Regions { labels, sizes }
+    }
+}
+
+impl<T> Map<T> {
+    /// The fewest steps from the nearest of `sources` to every cell, moving only through cells
+    /// that are `passable`. Unreachable cells, and impassable sources, are None.
+    pub fn distances(
+        &self,
+        sources: impl IntoIterator<Item = Coord>,
+        connectivity: Connectivity,
+        passable: impl Fn(&T) -> bool,
+    ) -> Map<Option<u32>> {
+        let mut dist = Map::empty(self.width, self.height, None);
+        let mut queue = VecDeque::new();
+        for c in sources {
+            if self.get_ref(c).is_some_and(&passable) && dist[c].is_none() {
+                dist[c] = Some(0);
+                queue.push_back(c);
+            }
+        }
+
+        while let Some(c) = queue.pop_front() {
+            let d = dist[c].unwrap();
+            for next in connectivity.neighbors(c) {
+                if self.get_ref(next).is_some_and(&passable) && dist[next].is_none() {
+                    dist[next] = Some(d + 1);
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        dist
+    }
+}
+
+impl<T: Clone> Map<T> {
+    /// `n` by `n` copies of the map side by side, for walking a map that repeats forever. Tile
+    /// (i, j) starts at (i * width, j * height).
+    pub fn tiled(&self, n: usize) -> Self {
+        Self::build(self.width * n, self.height * n, |x, y| {
+            self[(x % self.width, y % self.height)].clone()
+        })
+    }
+}
+
+/// Queries on a distance field from [`Map::distances`].
+impl Map<Option<u32>> {
+    /// The number of cells at most `n` steps away.
+    pub fn count_within(&self, n: u32) -> usize {
+        self.data.iter().filter(|d| d.is_some_and(|d| d <= n)).count()
+    }
+
+    /// The number of cells a walk of exactly `n` steps can end on. With four-way movement every
+    /// step changes the parity of x + y, so these are the cells within `n` steps whose distance
+    /// has the same parity as `n`: any leftover steps can be spent going back and forth.
+    pub fn count_exactly(&self, n: u32) -> usize {
+        self.data.iter().filter(|d| d.is_some_and(|d| d <= n && d % 2 == n % 2)).count()
+    }
+}
+
+/// Panics if `c` is outside the map.
+impl<T> Index<Coord> for Map<T> {
+    type Output = T;
+
+    fn index(&self, c: Coord) -> &T {
+        match self.index_of(c) {
+            Some(i) => &self.data[i],
+            None => panic!("{c} out of bounds for a {}x{} map", self.width, self.height),
+        }
+    }
+}
+
+impl<T> IndexMut<Coord> for Map<T> {
+    fn index_mut(&mut self, c: Coord) -> &mut T {
+        match self.index_of(c) {
+            Some(i) => &mut self.data[i],
+            None => panic!("{c} out of bounds for a {}x{} map", self.width, self.height),
+        }
+    }
+}
+
+/// Indexes by `(x, y)`. Panics if either is out of bounds.
+impl<T> Index<(usize, usize)> for Map<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        &self.row(y)[x]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Map<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        &mut self.row_mut(y)[x]
     }
 }
 
@@ -153,7 +739,7 @@ where
     T: Display
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let out = self.data.iter()
+        let out = self.rows()
             .map(|line|
                 line.iter()
                 .map(|v| v.to_string())
@@ -165,3 +751,548 @@ where
         write!(f, "{out}")
     }
 }
+
+/// An axis-aligned rectangle of cells. Both corners are inclusive.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Rect {
+    pub min: Coord,
+    pub max: Coord,
+}
+
+impl Rect {
+    /// The rectangle spanning two opposite corners, given in any order.
+    pub fn new(a: Coord, b: Coord) -> Self {
+        Rect {
+            min: Coord::new(a.x.min(b.x), a.y.min(b.y)),
+            max: Coord::new(a.x.max(b.x), a.y.max(b.y)),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        (self.max.x - self.min.x) as usize + 1
+    }
+
+    pub fn height(&self) -> usize {
+        (self.max.y - self.min.y) as usize + 1
+    }
+
+    pub fn contains(&self, c: Coord) -> bool {
+        (self.min.x..=self.max.x).contains(&c.x) && (self.min.y..=self.max.y).contains(&c.y)
+    }
+
+    /// The smallest rectangle covering both.
+    pub fn union(&self, other: &Rect) -> Rect {
+        Rect::new(
+            Coord::new(self.min.x.min(other.min.x), self.min.y.min(other.min.y)),
+            Coord::new(self.max.x.max(other.max.x), self.max.y.max(other.max.y)),
+        )
+    }
+
+    /// Every cell inside, in reading order.
+    pub fn points(&self) -> impl Iterator<Item = Coord> {
+        let Rect { min, max } = *self;
+        (min.y..=max.y).flat_map(move |y| (min.x..=max.x).map(move |x| Coord::new(x, y)))
+    }
+}
+
+/// A grid with no fixed size, where only cells that have been set are stored. Every other cell
+/// holds the background value. Coordinates can be negative, so it suits worlds that grow in any
+/// direction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseMap<T> {
+    data: HashMap<Coord, T>,
+    background: T,
+
+    // Grows as cells are set but never shrinks, so removing a cell leaves it as it was.
+    bounds: Option<Rect>,
+}
+
+impl<T> SparseMap<T> {
+    pub fn new(background: T) -> Self {
+        SparseMap { data: HashMap::new(), background, bounds: None }
+    }
+
+    /// The number of cells that have been set.
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub fn background(&self) -> &T {
+        &self.background
+    }
+
+    /// A rectangle around every cell ever set, or None if nothing has been.
+    pub fn bounds(&self) -> Option<Rect> {
+        self.bounds
+    }
+
+    /// The value at `c`, which is the background unless the cell has been set.
+    pub fn get(&self, c: Coord) -> &T {
+        self.data.get(&c).unwrap_or(&self.background)
+    }
+
+    /// True if `c` has been set, even if to the background value.
+    pub fn contains(&self, c: Coord) -> bool {
+        self.data.contains_key(&c)
+    }
+
+    pub fn insert(&mut self, c: Coord, v: T) -> Option<T> {
+        let point = Rect::new(c, c);
+        self.bounds = Some(self.bounds.map_or(point, |b| b.union(&point)));
+        self.data.insert(c, v)
+    }
+
+    /// Returns `c` to the background.
+    pub fn remove(&mut self, c: Coord) -> Option<T> {
+        self.data.remove(&c)
+    }
+
+    /// The cells that have been set, in reading order.
+    pub fn iter(&self) -> impl Iterator<Item = (Coord, &T)> {
+        let mut cells: Vec<_> = self.data.iter().map(|(&c, v)| (c, v)).collect();
+        cells.sort_by_key(|(c, _)| (c.y, c.x));
+        cells.into_iter()
+    }
+}
+
+impl<T: Clone> SparseMap<T> {
+    /// Every cell inside [`SparseMap::bounds`] as a dense map. Map (0, 0) is the bounds' top left
+    /// corner, so it is rarely the origin; an empty sparse map gives an empty map.
+    pub fn to_map(&self) -> Map<T> {
+        let Some(bounds) = self.bounds else {
+            return Map::empty(0, 0, self.background.clone());
+        };
+
+        Map::build(bounds.width(), bounds.height(), |x, y| {
+            self.get(bounds.min + Coord::from((x, y))).clone()
+        })
+    }
+}
+
+impl<T: Clone + PartialEq> SparseMap<T> {
+    /// Stores the cells of `map` that aren't `background`, with map (0, 0) at the origin.
+    pub fn from_map(map: &Map<T>, background: T) -> Self {
+        let mut sparse = SparseMap::new(background);
+        for (c, v) in map.iter() {
+            if *v != sparse.background {
+                sparse.insert(c, v.clone());
+            }
+        }
+        sparse
+    }
+}
+
+/// Draws the cells inside the bounds, like [`Map`]'s Display.
+impl<T: Clone + Display> Display for SparseMap<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.to_map().fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn distances() {
+        let (a, b) = (Coord::new(1, -2), Coord::new(-3, 5));
+        assert_eq!(a.manhattan(b), 11);
+        assert_eq!(a.chebyshev(b), 7);
+        assert_eq!(b.chebyshev(a), 7);
+        assert_eq!(a.chebyshev(a), 0);
+        assert_eq!(Coord::new(2, 2).chebyshev(Coord::new(3, 3)), 1);
+    }
+
+    #[test]
+    fn neighbors_go_clockwise_from_north() {
+        let c = Coord::new(5, 5);
+        assert_eq!(
+            c.neighbors4().collect::<Vec<_>>(),
+            [(5, 4), (6, 5), (5, 6), (4, 5)].map(Coord::from),
+        );
+        assert_eq!(
+            c.neighbors8().collect::<Vec<_>>(),
+            [(5, 4), (6, 4), (6, 5), (6, 6), (5, 6), (4, 6), (4, 5), (4, 4)].map(Coord::from),
+        );
+        assert!(c.neighbors8().all(|n| n.chebyshev(c) == 1));
+    }
+
+    #[test]
+    fn rotations() {
+        // y points south, so a clockwise turn takes north to east.
+        let north = Coord::new(0, -1);
+        assert_eq!(north.rotate_cw(), Coord::new(1, 0));
+        assert_eq!(north.rotate_ccw(), Coord::new(-1, 0));
+
+        let c = Coord::new(3, -7);
+        assert_eq!(c.rotate_cw().rotate_ccw(), c);
+        assert_eq!(c.rotate_cw().rotate_cw().rotate_cw().rotate_cw(), c);
+
+        let center = Coord::new(2, 2);
+        assert_eq!(Coord::new(2, 0).rotate_cw_about(center), Coord::new(4, 2));
+        assert_eq!(Coord::new(4, 2).rotate_cw_about(center), Coord::new(2, 4));
+        assert_eq!(Coord::new(2, 0).rotate_ccw_about(center), Coord::new(0, 2));
+        assert_eq!(center.rotate_cw_about(center), center);
+    }
+
+    #[test]
+    fn arithmetic_and_conversions() {
+        let mut c = Coord::new(1, 2) + Coord::new(3, 4) * 2 - Coord::new(1, 1);
+        assert_eq!(c, Coord::new(6, 9));
+        c += Coord::new(1, 1);
+        c -= Coord::new(0, 5);
+        assert_eq!(-c, Coord::new(-7, -5));
+
+        assert_eq!(<(i32, i32)>::from(c), (7, 5));
+        assert_eq!(<(usize, usize)>::try_from(c), Ok((7, 5)));
+        assert!(<(usize, usize)>::try_from(Coord::new(0, -1)).is_err());
+        assert_eq!(Coord::from((3usize, 4usize)), Coord::new(3, 4));
+    }
+
+    #[test]
+    fn direction_sets() {
+        assert_eq!(
+            Direction::all().map(|d| d.delta()),
+            [(0, -1), (1, 0), (0, 1), (-1, 0)].map(Coord::from),
+        );
+        assert!(Direction::all().iter().all(|d| !d.is_diagonal()));
+
+        let all8 = Direction::all8();
+        assert_eq!(all8.map(|d| d.delta()).to_vec(), Coord::new(0, 0).neighbors8().collect::<Vec<_>>());
+        assert_eq!(all8.iter().filter(|d| d.is_diagonal()).count(), 4);
+        for d in all8 {
+            let kinds = [d.is_vertical(), d.is_horizontal(), d.is_diagonal()];
+            assert_eq!(kinds.iter().filter(|&&k| k).count(), 1, "{d:?}");
+        }
+    }
+
+    #[test]
+    fn opposites_cancel() {
+        for d in Direction::all8() {
+            assert_eq!(d.opposite().opposite(), d);
+            assert_eq!(d.delta() + d.opposite().delta(), Coord::new(0, 0));
+        }
+        assert_eq!(Direction::NorthEast.opposite(), Direction::SouthWest);
+    }
+
+    #[test]
+    fn turning_walks_the_compass() {
+        assert_eq!(Direction::North.turn_right(), Direction::East);
+        assert_eq!(Direction::North.turn_left(), Direction::West);
+        assert_eq!(Direction::NorthEast.turn_right(), Direction::SouthEast);
+        assert_eq!(Direction::NorthWest.turn_left(), Direction::SouthWest);
+
+        for d in Direction::all8() {
+            assert_eq!(d.turn_left().turn_right(), d);
+            assert_eq!(d.turn_right().turn_right(), d.opposite());
+            // turning the direction turns its step the same way
+            assert_eq!(d.turn_right().delta(), d.delta().rotate_cw());
+            assert_eq!(d.turn_left().delta(), d.delta().rotate_ccw());
+        }
+    }
+
+    #[test]
+    fn parses_directions() {
+        for (chars, d) in [
+            ("UN^", Direction::North),
+            ("RE>", Direction::East),
+            ("DSv", Direction::South),
+            ("LW<", Direction::West),
+        ] {
+            for c in chars.chars() {
+                assert_eq!(Direction::try_from(c), Ok(d));
+                assert_eq!(c.to_string().parse(), Ok(d));
+            }
+        }
+
+        assert!(Direction::try_from('x').is_err());
+        assert!("".parse::<Direction>().is_err());
+        assert!("UR".parse::<Direction>().is_err());
+    }
+
+    fn chars(rows: &[&str]) -> Map<char> {
+        char_map_from_strings(rows.iter().map(|r| r.to_string()).collect()).unwrap()
+    }
+
+    #[test]
+    fn new_rejects_ragged_rows() {
+        let map = Map::new(vec![vec![1, 2], vec![3, 4]]).unwrap();
+        assert_eq!((map.width(), map.height()), (2, 2));
+
+        let error = Map::new(vec![vec![1, 2], vec![3, 4], vec![5]]).unwrap_err();
+        assert!(matches!(error, Error::Parse { line: Some(3), .. }), "{error:?}");
+        assert!(Map::new(vec![vec![1], vec![2, 3]]).is_err());
+    }
+
+    #[test]
+    fn char_maps_need_a_cell() {
+        assert_eq!(chars(&["ab", "cd"])[(1, 0)], 'b');
+        assert!(matches!(char_map_from_strings(vec![String::new()]), Err(Error::Missing { .. })));
+        assert!(matches!(
+            char_map_from_strings(vec!["ab".to_string(), "c".to_string()]),
+            Err(Error::Parse { line: Some(2), .. }),
+        ));
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn indexing_outside_panics() {
+        let map = chars(&["ab", "cd"]);
+        let _ = map[Coord::new(2, 0)];
+    }
+
+    #[test]
+    fn columns_run_top_to_bottom() {
+        let map = chars(&["abc", "def"]);
+        assert_eq!(map.column(1).collect::<String>(), "be");
+        assert_eq!(map.columns().map(|c| c.collect::<String>()).collect::<Vec<_>>(), ["ad", "be", "cf"]);
+        assert_eq!(map.rows().map(|r| r.iter().collect::<String>()).collect::<Vec<_>>(), ["abc", "def"]);
+        assert_eq!(map[Coord::new(2, 1)], 'f');
+        assert_eq!(map.get(Coord::new(-1, 0)), None);
+    }
+
+    fn lines(map: &Map<char>) -> Vec<String> {
+        map.rows().map(|r| r.iter().collect()).collect()
+    }
+
+    #[test]
+    fn rotations_of_a_wide_map() {
+        let map = chars(&["abc", "def"]);
+
+        let cw = map.rotate_cw();
+        assert_eq!((cw.width(), cw.height()), (2, 3));
+        assert_eq!(lines(&cw), ["da", "eb", "fc"]);
+        assert_eq!(lines(&map.rotate_ccw()), ["cf", "be", "ad"]);
+        assert_eq!(lines(&map.rotate_180()), ["fed", "cba"]);
+        assert_eq!(lines(&map.transpose()), ["ad", "be", "cf"]);
+
+        assert_eq!(map.rotate_cw().rotate_cw().rotate_cw().rotate_cw(), map);
+        assert_eq!(map.rotate_cw().rotate_ccw(), map);
+        assert_eq!(map.rotate_cw().rotate_cw(), map.rotate_180());
+        assert_eq!(map.transpose().transpose(), map);
+
+        let mut flipped = map.transpose();
+        flipped.flip_horizontal();
+        assert_eq!(flipped, map.rotate_cw());
+    }
+
+    #[test]
+    fn flips_in_place() {
+        let mut map = chars(&["abc", "def", "ghi"]);
+        map.flip_horizontal();
+        assert_eq!(lines(&map), ["cba", "fed", "ihg"]);
+        map.flip_vertical();
+        assert_eq!(lines(&map), ["ihg", "fed", "cba"]);
+
+        let mut wide = chars(&["abc", "def"]);
+        wide.flip_vertical();
+        assert_eq!(lines(&wide), ["def", "abc"]);
+        wide.flip_horizontal();
+        assert_eq!(wide, chars(&["abc", "def"]).rotate_180());
+    }
+
+    #[test]
+    fn transforms_of_an_empty_map() {
+        let empty = Map::<char>::default();
+        assert!(empty.rotate_cw().is_empty());
+        assert!(empty.rotate_ccw().is_empty());
+        assert!(empty.rotate_180().is_empty());
+        assert!(empty.transpose().is_empty());
+
+        let mut flipped = empty.clone();
+        flipped.flip_horizontal();
+        flipped.flip_vertical();
+        assert_eq!(flipped, empty);
+    }
+
+    #[test]
+    fn slide_stops_at_walls_and_other_rocks() {
+        let mut map = chars(&[".O.#O", "O..O.", "..O.."]);
+        let (movable, empty) = (|&c: &char| c == 'O', |&c: &char| c == '.');
+
+        map.slide(Direction::East, movable, empty);
+        assert_eq!(lines(&map), ["..O#O", "...OO", "....O"]);
+        map.slide(Direction::North, movable, empty);
+        assert_eq!(lines(&map), ["..O#O", "...OO", "....O"]);
+        map.slide(Direction::South, movable, empty);
+        assert_eq!(lines(&map), ["...#O", "....O", "..OOO"]);
+        map.slide(Direction::West, movable, empty);
+        assert_eq!(lines(&map), ["...#O", "O....", "OOO.."]);
+    }
+
+    #[test]
+    fn rect_corners_in_any_order() {
+        let rect = Rect::new(Coord::new(2, -1), Coord::new(-1, 3));
+        assert_eq!((rect.min, rect.max), (Coord::new(-1, -1), Coord::new(2, 3)));
+        assert_eq!((rect.width(), rect.height()), (4, 5));
+
+        assert!(rect.contains(Coord::new(-1, -1)));
+        assert!(rect.contains(Coord::new(2, 3)));
+        assert!(rect.contains(Coord::new(0, 0)));
+        assert!(!rect.contains(Coord::new(3, 0)));
+        assert!(!rect.contains(Coord::new(0, -2)));
+
+        assert_eq!(rect.points().count(), 20);
+        assert_eq!(rect.points().next(), Some(rect.min));
+        assert_eq!(rect.union(&Rect::new(Coord::new(5, 5), Coord::new(5, 5))).max, Coord::new(5, 5));
+    }
+
+    #[test]
+    fn sparse_bounds_grow_into_negatives() {
+        let mut sparse = SparseMap::new('.');
+        assert_eq!(sparse.bounds(), None);
+        assert!(sparse.to_map().is_empty());
+
+        sparse.insert(Coord::new(1, 1), '#');
+        sparse.insert(Coord::new(-2, 0), '#');
+        sparse.insert(Coord::new(0, -1), '#');
+        assert_eq!(sparse.bounds(), Some(Rect::new(Coord::new(-2, -1), Coord::new(1, 1))));
+        assert_eq!(sparse.get(Coord::new(-2, 0)), &'#');
+        assert_eq!(sparse.get(Coord::new(-100, 7)), &'.');
+
+        // removing a cell leaves the bounds alone
+        sparse.remove(Coord::new(-2, 0));
+        assert_eq!(sparse.len(), 2);
+        assert_eq!(sparse.bounds(), Some(Rect::new(Coord::new(-2, -1), Coord::new(1, 1))));
+        assert_eq!(lines(&sparse.to_map()), ["..#.", "....", "...#"]);
+        assert_eq!(sparse.iter().map(|(c, _)| c).collect::<Vec<_>>(), [(0, -1), (1, 1)].map(Coord::from));
+    }
+
+    #[test]
+    fn sparse_round_trips_through_a_map() {
+        let map = chars(&["#..", ".#.", "..#"]);
+        let sparse = SparseMap::from_map(&map, '.');
+        assert_eq!(sparse.len(), 3);
+        assert!(sparse.contains(Coord::new(1, 1)));
+        assert!(!sparse.contains(Coord::new(1, 0)));
+        assert_eq!(sparse.to_map(), map);
+        assert_eq!(sparse.to_string(), map.to_string());
+    }
+
+    /// Distances as digits, with `#` for unreachable cells.
+    fn digits(dist: &Map<Option<u32>>) -> Vec<String> {
+        dist.rows()
+            .map(|r| r.iter().map(|d| d.map_or('#', |d| char::from_digit(d, 36).unwrap())).collect())
+            .collect()
+    }
+
+    #[test]
+    fn counts_on_an_open_grid() {
+        let map = chars(&["...", ".S.", "..."]);
+        let dist = map.distances([map.find(&'S')], Connectivity::Four, |&c| c != '#');
+        assert_eq!(digits(&dist), ["212", "101", "212"]);
+
+        assert_eq!(dist.count_within(0), 1);
+        assert_eq!(dist.count_within(1), 5);
+        assert_eq!(dist.count_within(2), 9);
+        assert_eq!(dist.count_exactly(1), 4);
+        // back and forth to the start, or out to a corner
+        assert_eq!(dist.count_exactly(2), 5);
+        assert_eq!(dist.count_exactly(3), 4);
+
+        let dist = map.distances([map.find(&'S')], Connectivity::Eight, |&c| c != '#');
+        assert_eq!(digits(&dist), ["111", "101", "111"]);
+    }
+
+    #[test]
+    fn counts_around_walls() {
+        let map = chars(&["S#.", ".#.", "..."]);
+        let dist = map.distances([Coord::new(0, 0)], Connectivity::Four, |&c| c != '#');
+        assert_eq!(digits(&dist), ["0#6", "1#5", "234"]);
+
+        assert_eq!(dist.count_within(4), 5);
+        assert_eq!(dist.count_within(100), 7);
+        assert_eq!(dist.count_exactly(4), 3);
+        assert_eq!(dist.count_exactly(5), 3);
+        assert_eq!(dist.count_exactly(6), 4);
+
+        // walls can't be sources, and several sources take the nearest
+        let sources = [Coord::new(1, 0), Coord::new(2, 0)];
+        let dist = map.distances(sources, Connectivity::Four, |&c| c != '#');
+        assert_eq!(digits(&dist), ["6#0", "5#1", "432"]);
+    }
+
+    #[test]
+    fn tiled_distances() {
+        let map = chars(&["S#", ".."]);
+        let tiled = map.tiled(3);
+        assert_eq!(lines(&tiled), ["S#S#S#", "......", "S#S#S#", "......", "S#S#S#", "......"]);
+
+        // from the middle tile's S
+        let dist = tiled.distances([Coord::new(2, 2)], Connectivity::Four, |&c| c != '#');
+        assert_eq!(digits(&dist), ["4#2#4#", "321234", "4#0#4#", "321234", "4#2#4#", "543456"]);
+        assert_eq!(dist.count_exactly(4), 17);
+    }
+
+    #[test]
+    fn wraparound() {
+        let map = chars(&["abc", "def"]);
+        assert_eq!(map.wrap_coord(Coord::new(-1, -1)), Some(Coord::new(2, 1)));
+        assert_eq!(map.wrap_coord(Coord::new(7, 4)), Some(Coord::new(1, 0)));
+        assert_eq!(map.get_with_wraparound(Coord::new(-4, 3)), Some('f'));
+
+        let empty = Map::<char>::default();
+        assert_eq!(empty.wrap_coord(Coord::new(1, 1)), None);
+        assert_eq!(empty.get_with_wraparound(Coord::new(1, 1)), None);
+        // no columns, but still a row
+        let hollow = Map::<char>::new(vec![vec![]]).unwrap();
+        assert_eq!(hollow.wrap_coord(Coord::new(0, 0)), None);
+    }
+
+    #[test]
+    fn connectivity_neighbors() {
+        let c = Coord::new(0, 0);
+        let four: Vec<_> = Connectivity::Four.neighbors(c).collect();
+        let eight: Vec<_> = Connectivity::Eight.neighbors(c).collect();
+        assert_eq!(four, c.neighbors4().collect::<Vec<_>>());
+        assert_eq!(eight, c.neighbors8().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn flood_fill_crosses_diagonal_gaps_only_with_eight() {
+        let map = chars(&[".#.", "#.#", "..#"]);
+        let open = |&c: &char| c == '.';
+
+        assert_eq!(map.flood_fill(Coord::new(0, 0), Connectivity::Four, open), [Coord::new(0, 0)]);
+
+        let mut eight = map.flood_fill(Coord::new(0, 0), Connectivity::Eight, open);
+        eight.sort_by_key(|c| (c.y, c.x));
+        assert_eq!(eight, [(0, 0), (2, 0), (1, 1), (0, 2), (1, 2)].map(Coord::from));
+
+        let mut four = map.flood_fill(Coord::new(1, 1), Connectivity::Four, open);
+        four.sort_by_key(|c| (c.y, c.x));
+        assert_eq!(four, [(1, 1), (0, 2), (1, 2)].map(Coord::from));
+    }
+
+    #[test]
+    fn flood_fill_from_a_wall_or_off_the_map_is_empty() {
+        let map = chars(&[".#", "#."]);
+        let open = |&c: &char| c == '.';
+
+        assert_eq!(map.flood_fill(Coord::new(1, 0), Connectivity::Eight, open), []);
+        assert_eq!(map.flood_fill(Coord::new(-1, 0), Connectivity::Eight, open), []);
+        assert_eq!(map.flood_fill(Coord::new(5, 5), Connectivity::Four, open), []);
+    }
+
+    #[test]
+    fn regions_are_numbered_in_reading_order() {
+        let map = chars(&["aab", "abb", "cbb"]);
+        let regions = map.regions(Connectivity::Four, |a, b| a == b);
+        let labels = Map::new(vec![vec![0, 0, 1], vec![0, 1, 1], vec![2, 1, 1]]).unwrap();
+        assert_eq!(regions.labels, labels);
+        assert_eq!(regions.sizes, [3, 5, 1]);
+
+        let map = chars(&[".#", "#."]);
+        let four = map.regions(Connectivity::Four, |a, b| a == b);
+        assert_eq!(four.labels, Map::new(vec![vec![0, 1], vec![2, 3]]).unwrap());
+        assert_eq!(four.sizes, [1, 1, 1, 1]);
+
+        let eight = map.regions(Connectivity::Eight, |a, b| a == b);
+        assert_eq!(eight.labels, Map::new(vec![vec![0, 1], vec![1, 0]]).unwrap());
+        assert_eq!(eight.sizes, [2, 2]);
+    }
+}
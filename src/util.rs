@@ -1,16 +1,79 @@
 use std::str::FromStr;
 
+use crate::error::{Error, Result};
+
 pub fn split_to_strings(s: &str) -> Vec<String> {
     s.split_whitespace()
         .map(|s| s.to_string())
         .collect()
 }
 
-pub fn split_parse<T>(s: &str) -> Vec<T>
+/// Parses every whitespace-separated token in `s`. On failure, the error points at the column of
+/// the offending token.
+pub fn split_parse<T>(s: &str) -> Result<Vec<T>>
 where
-    T: FromStr, <T as std::str::FromStr>::Err: std::fmt::Debug
+    T: FromStr
 {
     s.split_whitespace()
-        .map(|s| s.parse::<T>().unwrap())
+        .map(|token| {
+            // split_whitespace hands out subslices of s, so the offset is recoverable.
+            let offset = token.as_ptr() as usize - s.as_ptr() as usize;
+            parse(token).map_err(|e| e.at_column(offset + 1))
+        })
+        .collect()
+}
+
+/// Parses a single token, naming the expected type in the error.
+pub fn parse<T>(s: &str) -> Result<T>
+where
+    T: FromStr
+{
+    s.parse::<T>()
+        .map_err(|_| Error::parse(std::any::type_name::<T>(), s))
+}
+
+/// Runs `f` over each line, tagging any error with the (1-based) line it came from.
+pub fn map_lines<T, F>(lines: &[String], f: F) -> Result<Vec<T>>
+where
+    F: FnMut(&str) -> Result<T>
+{
+    map_lines_from(lines, 1, f)
+}
+
+/// Like [`map_lines`], for lines that start at line `first` of the input rather than line 1.
+pub fn map_lines_from<T, F>(lines: &[String], first: usize, mut f: F) -> Result<Vec<T>>
+where
+    F: FnMut(&str) -> Result<T>
+{
+    lines.iter()
+        .enumerate()
+        .map(|(i, line)| f(line).map_err(|e| e.at_line(first + i)))
         .collect()
 }
+
+/// The (1-based) line that paragraph `n` starts on, for paragraphs split with
+/// [`crate::puzzle::Paragraphs`].
+pub fn paragraph_line(paragraphs: &[Vec<String>], n: usize) -> usize {
+    paragraphs[..n].iter().map(|p| p.len() + 1).sum::<usize>() + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::puzzle::{Delimiter, Paragraphs};
+
+    #[test]
+    fn paragraph_lines_count_blank_lines() {
+        // line 1: a, 2: b, 3: blank, 4: c, 5: blank, 6: blank, 7: d
+        let blocks = Paragraphs.split("a\nb\n\nc\n\n\nd");
+        assert_eq!(blocks.len(), 4);
+        assert_eq!((0..4).map(|n| paragraph_line(&blocks, n)).collect::<Vec<_>>(), [1, 4, 6, 7]);
+    }
+
+    #[test]
+    fn errors_in_later_paragraphs_keep_their_line() {
+        let blocks = Paragraphs.split("1\n2\n\n\n3\nx");
+        let error = map_lines_from(&blocks[2], paragraph_line(&blocks, 2), parse::<u32>).unwrap_err();
+        assert!(matches!(error, Error::Parse { line: Some(6), .. }), "{error:?}");
+    }
+}